@@ -0,0 +1,152 @@
+//! Benchmarks the computation of dates. Each expression is measured with the
+//! engine as well as with a reference that scans the days one by one like the
+//! engine did before it computed dates arithmetically. In order to compare two
+//! revisions of the engine, run `cargo bench -- --save-baseline <name>` on the
+//! first and `cargo bench -- --baseline <name>` on the second revision.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use cron_lingo::Schedule;
+use std::str::FromStr;
+use time::macros::{datetime, time};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, Time};
+
+// Expressions that exercise the different rules of a schedule.
+const EXPRESSIONS: &[&str] = &[
+    "at 6 AM on Mondays and Thursdays",
+    "at 6 PM on the last Monday in odd weeks",
+    "at 6 AM, 12 PM and 6 PM on the first Monday and the second-to-last Friday",
+    "every 15 minutes between 9 AM and 5 PM on weekdays",
+    "at 9 AM on the 1st and 15th of the month in January, April, July and October",
+    "at 6 AM on Saturdays every 3rd week starting 2024-01-01",
+    "at 10 AM on Thursdays in weeks 10 to 20",
+    "at 6 PM on the last business day of the month",
+];
+
+// Returns the times of day of the expressions above in ascending order,
+// which the reference requires.
+fn times_of_day(expression: &str) -> Vec<Time> {
+    match expression {
+        "at 6 AM, 12 PM and 6 PM on the first Monday and the second-to-last Friday" => {
+            vec![time!(06:00), time!(12:00), time!(18:00)]
+        }
+        "every 15 minutes between 9 AM and 5 PM on weekdays" => (0..=32)
+            .map(|i| time!(09:00) + Duration::minutes(15 * i))
+            .collect(),
+        "at 9 AM on the 1st and 15th of the month in January, April, July and October" => {
+            vec![time!(09:00)]
+        }
+        "at 10 AM on Thursdays in weeks 10 to 20" => vec![time!(10:00)],
+        _ if expression.starts_with("at 6 AM") => vec![time!(06:00)],
+        _ => vec![time!(18:00)],
+    }
+}
+
+// Computes the next date after the base date by checking every time of day of
+// every day that follows, i.e. the day-by-day scan that the arithmetic engine
+// replaced.
+fn scan_next_date(schedule: &Schedule, times: &[Time], base: OffsetDateTime) -> OffsetDateTime {
+    let mut date = base.date();
+
+    loop {
+        for time in times {
+            let candidate = PrimitiveDateTime::new(date, *time).assume_offset(base.offset());
+
+            if candidate > base && schedule.matches(candidate) {
+                return candidate;
+            }
+        }
+
+        date = date.next_day().unwrap();
+    }
+}
+
+// Computes the next 100 dates of each expression with the engine as well as
+// with the reference.
+fn next_dates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("next 100 dates");
+    let start = datetime!(2024-01-01 00:00 UTC);
+
+    for expression in EXPRESSIONS {
+        let schedule = Schedule::from_str(expression).unwrap();
+        let times = times_of_day(expression);
+
+        // Both approaches must agree for the comparison to be meaningful.
+        let mut current = start;
+
+        for date in schedule.iter_from(start).take(100) {
+            current = scan_next_date(&schedule, &times, current);
+            assert_eq!(date.unwrap(), current, "{}", expression);
+        }
+
+        group.bench_function(BenchmarkId::new("arithmetic", expression), |b| {
+            b.iter(|| {
+                schedule
+                    .iter_from(black_box(start))
+                    .take(100)
+                    .for_each(|date| {
+                        black_box(date.unwrap());
+                    })
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("day-by-day scan", expression), |b| {
+            b.iter(|| {
+                let mut current = black_box(start);
+
+                for _ in 0..100 {
+                    current = black_box(scan_next_date(&schedule, &times, current));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// Computes the next 100 dates of a combination of all expressions.
+fn next_dates_multi(c: &mut Criterion) {
+    let multi = EXPRESSIONS
+        .iter()
+        .map(|expression| Schedule::from_str(expression).unwrap())
+        .fold(None, |multi, schedule| match multi {
+            None => Some(cron_lingo::schedule::MultiSchedule::from(schedule)),
+            Some(multi) => Some(multi + schedule),
+        })
+        .unwrap();
+
+    c.bench_function("next 100 dates of all expressions", |b| {
+        b.iter(|| {
+            multi
+                .iter_from(black_box(datetime!(2024-01-01 00:00 UTC)))
+                .take(100)
+                .for_each(|date| {
+                    black_box(date.unwrap());
+                })
+        })
+    });
+}
+
+// Computes the next 100 dates of a combination of a thousand schedules.
+fn next_dates_many(c: &mut Criterion) {
+    let multi = (0..1000)
+        .map(|i| format!("at {}:{:02} on Mondays and Thursdays", i % 24, i % 60))
+        .map(|expression| Schedule::from_str(&expression).unwrap())
+        .fold(None, |multi, schedule| match multi {
+            None => Some(cron_lingo::schedule::MultiSchedule::from(schedule)),
+            Some(multi) => Some(multi + schedule),
+        })
+        .unwrap();
+
+    c.bench_function("next 100 dates of 1000 schedules", |b| {
+        b.iter(|| {
+            multi
+                .iter_from(black_box(datetime!(2024-01-01 00:00 UTC)))
+                .take(100)
+                .for_each(|date| {
+                    black_box(date.unwrap());
+                })
+        })
+    });
+}
+
+criterion_group!(benches, next_dates, next_dates_multi, next_dates_many);
+criterion_main!(benches);
@@ -0,0 +1,337 @@
+//! Holiday calendars that can be attached to an iterator in order to
+//! exclude holidays (e.g. "except on public holidays") or to determine
+//! business days (e.g. "on the last business day of the month").
+use crate::error::*;
+use std::collections::{BTreeSet, HashSet};
+use std::iter::FromIterator;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use time::{Date, Duration};
+
+// Prepares a format description for dates in holiday lists, e.g. "2024-12-25".
+const LIST_DATE_FORMAT: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[year]-[month]-[day]");
+
+// Prepares a format description for dates in ICS files, e.g. "20241225".
+const ICS_DATE_FORMAT: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[year][month][day]");
+
+/// A calendar that decides whether a date is a holiday.
+///
+/// ```rust
+/// use cron_lingo::calendar::Calendar;
+/// use time::{Date, Month, Weekday};
+///
+/// // A calendar that considers every Friday the 13th a holiday.
+/// struct Superstition;
+///
+/// impl Calendar for Superstition {
+///     fn is_holiday(&self, date: Date) -> bool {
+///         date.weekday() == Weekday::Friday && date.day() == 13
+///     }
+/// }
+///
+/// let date = Date::from_calendar_date(2024, Month::December, 13).unwrap();
+/// assert!(Superstition.is_holiday(date));
+/// ```
+pub trait Calendar {
+    fn is_holiday(&self, date: Date) -> bool;
+}
+
+impl Calendar for HashSet<Date> {
+    fn is_holiday(&self, date: Date) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl Calendar for BTreeSet<Date> {
+    fn is_holiday(&self, date: Date) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl<C: Calendar + ?Sized> Calendar for Arc<C> {
+    fn is_holiday(&self, date: Date) -> bool {
+        (**self).is_holiday(date)
+    }
+}
+
+/// A policy that moves dates which do not fall on a business day to a
+/// business day nearby. A business day is a weekday from Monday to Friday
+/// that is not a holiday in any of the calendars attached to an iterator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RollPolicy {
+    /// Move to the next business day.
+    Following,
+    /// Move to the previous business day.
+    Preceding,
+    /// Move to the next business day unless it falls into the next month,
+    /// in which case move to the previous business day instead.
+    ModifiedFollowing,
+}
+
+/// A plain list of holidays, usually loaded from a file.
+///
+/// Two formats are supported. Either one date formatted as _YYYY-MM-DD_
+/// per line, where empty lines, lines starting with _#_ and anything that
+/// follows the date on the same line are ignored:
+///
+/// ```text
+/// # Public holidays 2024
+/// 2024-12-25 Christmas Day
+/// 2024-12-26 Boxing Day
+/// ```
+///
+/// Or a subset of the iCalendar format, where the _DTSTART_ (and optionally
+/// _DTEND_) properties of each _VEVENT_ are taken into account. All-day
+/// events that span several days are expanded into the respective dates.
+///
+/// ```rust
+/// use cron_lingo::calendar::{Calendar, HolidayList};
+/// use std::str::FromStr;
+/// use time::macros::date;
+///
+/// let holidays = HolidayList::from_str("2024-12-25\n2024-12-26").unwrap();
+/// assert!(holidays.is_holiday(date!(2024 - 12 - 25)));
+/// assert!(!holidays.is_holiday(date!(2024 - 12 - 27)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HolidayList(BTreeSet<Date>);
+
+impl HolidayList {
+    /// Create an empty list of holidays.
+    pub fn new() -> HolidayList {
+        HolidayList::default()
+    }
+
+    /// Read a list of holidays from a file. See `HolidayList` for the
+    /// supported formats.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<HolidayList, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| Error::Io {
+            path: path.to_path_buf(),
+            kind: e.kind(),
+            message: e.to_string(),
+        })?;
+        HolidayList::from_str(&content)
+    }
+
+    /// Add a single holiday to the list.
+    pub fn insert(&mut self, date: Date) {
+        self.0.insert(date);
+    }
+
+    /// Iterate the holidays in the list in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Date> {
+        self.0.iter()
+    }
+}
+
+impl Calendar for HolidayList {
+    fn is_holiday(&self, date: Date) -> bool {
+        self.0.contains(&date)
+    }
+}
+
+impl FromStr for HolidayList {
+    type Err = Error;
+
+    /// Parse a list of holidays from either of the supported formats.
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        if content.trim_start().starts_with("BEGIN:VCALENDAR") {
+            parse_ics(content)
+        } else {
+            parse_list(content)
+        }
+    }
+}
+
+impl FromIterator<Date> for HolidayList {
+    fn from_iter<I: IntoIterator<Item = Date>>(iter: I) -> Self {
+        HolidayList(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Date> for HolidayList {
+    fn extend<I: IntoIterator<Item = Date>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+// Parses a list of holidays with one date per line.
+fn parse_list(content: &str) -> Result<HolidayList, Error> {
+    let mut holidays = HolidayList::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let token = line.split_whitespace().next().unwrap();
+        let date = Date::parse(token, LIST_DATE_FORMAT).map_err(|_| invalid_line(index, line))?;
+
+        holidays.insert(date);
+    }
+
+    Ok(holidays)
+}
+
+// Parses the dates of all events in an iCalendar file.
+fn parse_ics(content: &str) -> Result<HolidayList, Error> {
+    let mut holidays = HolidayList::new();
+    let mut in_event = false;
+    let mut start = None;
+    let mut end = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+
+        let (name, value) = match line.split_once(':') {
+            Some((params, value)) => (params.split(';').next().unwrap(), value),
+            None => continue,
+        };
+
+        match (name, value) {
+            ("BEGIN", "VEVENT") => {
+                in_event = true;
+                start = None;
+                end = None;
+            }
+            ("END", "VEVENT") => {
+                let first = start.ok_or_else(|| invalid_line(index, line))?;
+
+                // The end date of an all-day event is exclusive.
+                let last = match end {
+                    Some(end) if end > first => end - Duration::days(1),
+                    _ => first,
+                };
+
+                let mut date = first;
+
+                while date <= last {
+                    holidays.insert(date);
+                    date = match date.next_day() {
+                        Some(date) => date,
+                        None => break,
+                    };
+                }
+
+                in_event = false;
+            }
+            ("DTSTART", value) if in_event => {
+                start = Some(parse_ics_date(value).ok_or_else(|| invalid_line(index, line))?);
+            }
+            // Only dates without times mark the end of all-day events.
+            ("DTEND", value) if in_event && value.len() == 8 => {
+                end = Some(parse_ics_date(value).ok_or_else(|| invalid_line(index, line))?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(holidays)
+}
+
+// Parses the date part of an iCalendar date or date-time value, e.g.
+// "20241225" or "20241225T000000Z".
+fn parse_ics_date(value: &str) -> Option<Date> {
+    let date = value.get(..8)?;
+    Date::parse(date, ICS_DATE_FORMAT).ok()
+}
+
+// Creates the error for an erroneous line in a list of holidays.
+fn invalid_line(index: usize, line: &str) -> Error {
+    Error::HolidayList(HolidayListError {
+        line: index + 1,
+        content: line.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_parse_list() {
+        let content = "# Public holidays\n\n2024-12-25 Christmas Day\n  2024-12-26\n";
+        let expected: HolidayList = vec![date!(2024 - 12 - 25), date!(2024 - 12 - 26)]
+            .into_iter()
+            .collect();
+        assert_eq!(HolidayList::from_str(content), Ok(expected));
+
+        assert_eq!(
+            HolidayList::from_str("2024-12-25\n25.12.2024"),
+            Err(Error::HolidayList(HolidayListError {
+                line: 2,
+                content: "25.12.2024".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_ics() {
+        let content = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+SUMMARY:Christmas\r
+DTSTART;VALUE=DATE:20241225\r
+DTEND;VALUE=DATE:20241227\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:New Year\r
+DTSTART:20250101T000000Z\r
+DTEND:20250101T235959Z\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let expected: HolidayList = vec![
+            date!(2024 - 12 - 25),
+            date!(2024 - 12 - 26),
+            date!(2025 - 01 - 01),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(HolidayList::from_str(content), Ok(expected));
+
+        // Events that end with the last representable date.
+        let content =
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:99991230\nDTEND:99991231\nEND:VEVENT\n\
+                       BEGIN:VEVENT\nDTSTART:99991231\nEND:VEVENT\nEND:VCALENDAR";
+        let expected: HolidayList = vec![date!(9999 - 12 - 30), date!(9999 - 12 - 31)]
+            .into_iter()
+            .collect();
+        assert_eq!(HolidayList::from_str(content), Ok(expected));
+
+        let content = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Unknown\nEND:VEVENT\nEND:VCALENDAR";
+        assert!(matches!(
+            HolidayList::from_str(content),
+            Err(Error::HolidayList(_))
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join("cron-lingo-test-holidays.txt");
+        std::fs::write(&path, "2024-12-25\n2024-12-26\n").unwrap();
+
+        let holidays = HolidayList::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(holidays.is_holiday(date!(2024 - 12 - 26)));
+        match HolidayList::load(&path) {
+            Err(Error::Io {
+                path: error_path,
+                kind,
+                ..
+            }) => {
+                assert_eq!(error_path, path);
+                assert_eq!(kind, std::io::ErrorKind::NotFound);
+            }
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
+    }
+}
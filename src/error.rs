@@ -1,5 +1,6 @@
 use std::error::Error as StdError;
 use std::fmt;
+use std::path::PathBuf;
 
 /// A global error type that encapsulates all other, more specific
 /// error types.
@@ -11,6 +12,14 @@ pub enum Error {
     UnexpectedEndOfInput,
     TimeParse(time::error::Parse),
     IndeterminateOffset(time::error::IndeterminateOffset),
+    UnknownCalendar(String),
+    HolidayList(HolidayListError),
+    Io {
+        path: PathBuf,
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    UnknownTimeZone(String),
 }
 
 impl fmt::Display for Error {
@@ -24,6 +33,19 @@ impl fmt::Display for Error {
             ),
             Self::TimeParse(e) => write!(f, "failed to parse time: {}", e),
             Self::IndeterminateOffset(e) => e.fmt(f),
+            Self::UnknownCalendar(name) => write!(
+                f,
+                "the expression refers to '{}', but no such holidays were attached to the iterator",
+                name
+            ),
+            Self::HolidayList(e) => e.fmt(f),
+            Self::Io { path, message, .. } => write!(
+                f,
+                "failed to read holiday list '{}': {}",
+                path.display(),
+                message
+            ),
+            Self::UnknownTimeZone(name) => write!(f, "unknown time zone '{}'", name),
         }
     }
 }
@@ -50,3 +72,23 @@ impl fmt::Display for SyntaxError {
 }
 
 impl StdError for SyntaxError {}
+
+/// Error that occurs when a list of holidays cannot be parsed. Gives the
+/// line number (starting at 1) and the content of the erroneous line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HolidayListError {
+    pub(crate) line: usize,
+    pub(crate) content: String,
+}
+
+impl fmt::Display for HolidayListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid holiday in line {}, expected a date formatted as YYYY-MM-DD or an iCalendar event, got '{}'",
+            self.line, self.content
+        )
+    }
+}
+
+impl StdError for HolidayListError {}
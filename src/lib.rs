@@ -6,6 +6,7 @@
 //!
 //! # Example
 //! ```rust
+//! use cron_lingo::schedule::MultiSchedule;
 //! use cron_lingo::Schedule;
 //! use std::str::FromStr;
 //! use time::macros::offset;
@@ -26,22 +27,78 @@
 //!     combination += schedule3;
 //!     assert!(combination.iter()?.next().is_some());
 //!
+//!     // Schedules can be labeled, e.g. with the name of a job, and each
+//!     // date of a combination comes with the label of the schedule that
+//!     // produced it.
+//!     let jobs = MultiSchedule::from((Schedule::from_str("at 2 AM")?, "backup"))
+//!         + (Schedule::from_str("at 6 PM on weekdays")?, "report");
+//!     let (_date, job) = jobs.iter()?.next().unwrap()?;
+//!     assert!(*job == "backup" || *job == "report");
+//!
+//!     // Schedules can be removed again, e.g. by their label.
+//!     let mut jobs = jobs;
+//!     jobs.remove_label(&"backup");
+//!     assert_eq!(jobs.len(), 1);
+//!
+//!     // A combination can also be turned into an iterator that owns
+//!     // the schedules, e.g. to move it to another thread.
+//!     let iter = combination.clone().into_iter();
+//!     std::thread::spawn(move || iter.take(3).count()).join().unwrap();
+//!
 //!     // The examples above assume that the current local offset is to
 //!     // be used to determine the dates, but dates can also be computed
 //!     // in different offsets.
 //!     let expr = "at 6:30 AM on Mondays and Thursdays";
 //!     let schedule = Schedule::from_str(expr)?;
 //!     assert!(schedule.iter()?.assume_offset(offset!(+3)).next().is_some());
+//!
+//!     // The local offset may not be available in multithreaded programs,
+//!     // so dates can also be computed in UTC or from an arbitrary start.
+//!     assert!(schedule.iter_utc().next().is_some());
+//!     let start = time::macros::datetime!(2024-01-01 00:00 UTC);
+//!     assert!(schedule.iter_from(start).next().is_some());
+//!
+//!     // Iterators can also move backwards, e.g. to find the last date
+//!     // before now.
+//!     assert!(schedule.iter_utc().prev().is_some());
+//!     assert!(schedule.rev_iter_from(start).next().is_some());
+//!
+//!     // Dates within a range can be iterated or counted.
+//!     let end = time::macros::datetime!(2025-01-01 00:00 UTC);
+//!     assert_eq!(schedule.occurrences_between(start, end).count(), 105);
+//!     assert_eq!(schedule.count_between(start, end)?, 105);
+//!
+//!     // Check if an instant is a date of the schedule.
+//!     assert!(schedule.matches(time::macros::datetime!(2024-03-04 06:30 UTC)));
 //!     Ok(())
 //! }
 //! ```
 //!
+//! # Time zones
+//!
+//! A fixed offset does not follow daylight saving time, so "at 6 AM" in e.g.
+//! Europe/Berlin would be off by one hour for half of the year. With the
+//! optional `tz` feature enabled, an iterator can compute dates in the
+//! wall-clock time of a time zone from the tz database instead, see
+//! `timezone::TimeZone` and `ScheduleIter::time_zone`. By default wall-clock
+//! times that occur twice when the clocks are turned back are used once at
+//! their first occurrence, while wall-clock times that are skipped when the
+//! clocks are turned forward are shifted forward by the length of the gap.
+//! See `ScheduleIter::on_overlap` and `ScheduleIter::on_gap` in order to
+//! change this behaviour.
+//!
+//! The zone may also be part of the expression itself, e.g. "at 9 AM
+//! Europe/London", in which case the iterator uses it instead of the local
+//! offset unless another zone is set on the iterator.
+//!
 //! # Expression syntax
 //!
-//! A single expression consists of three parts:
-//! a time specification, and optionally a weekday and week specification.
+//! A single expression consists of seven parts:
+//! a time specification, and optionally either a weekday, a day-of-month or a
+//! business day specification, a month specification, a week specification, a
+//! roll specification, an exception specification and a zone specification.
 //!
-//! > \<time spec\> [\<weekday spec\>] [\<week spec\>]
+//! > \<time spec\> [\<weekday spec\> | \<day-of-month spec\> | \<business day spec\>] [\<month spec\>] [\<week spec\>] [\<roll spec\>] [\<exception spec\>] [\<zone spec\>]
 //!
 //! Here are a few random examples of complete expressions:
 //!
@@ -52,16 +109,30 @@
 //! * at 6:30 AM on Mondays
 //! * at 6 AM, 6 PM (Mondays)
 //! * at 8 AM on the first Sunday
+//! * every 15 minutes between 9 AM and 5 PM on Mondays
+//! * at 9 AM on the 1st and 15th of the month
+//! * at 8 AM on the first Monday in January, April, July and October
+//! * at 6 AM on weekdays except on public holidays and December 24
+//! * at 6 PM on the last business day of the month
+//! * at 9 AM on the 1st of the month or the following business day
+//! * at 9 AM on Mondays Europe/London
 //!
 //! This table gives some more examples for each type of specification in a block:
 //!
 //! | Times                  | Weekday (optional)           | Week (optional)  |
 //! | ---------------------- | ---------------------------- | ---------------- |
 //! | at every full hour     | on Mondays and Tuesdays      | in odd weeks     |
+//! | every 30 minutes       | on Fridays                   |                  |
+//! | every 2 hours between 8 AM and 6 PM | on Mondays      |                  |
 //! | at 7:30 AM and 7:30 PM | on Tuesdays, Saturdays       | in even weeks    |
 //! | at 6 AM, 6 PM and 8 PM | on Fridays                   |                  |
 //! | at 6 AM, 12 AM, 6 PM   |                              |                  |
 //! | at 8:30 AM             |                              | in odd weeks     |
+//! | at 8 AM                | on Mondays                   | every other week starting 2024-01-01 |
+//! | at 8 AM                | on Fridays                   | every 3rd week starting 2024-01-05 |
+//! | at 8 AM                | on Thursdays                 | in weeks 10 to 20 |
+//! | at 18:30               | on Fridays                   |                  |
+//! | at 7:30, 12 PM, 23:45  | on Mondays                   |                  |
 //! | at 8 AM                | on Wednesdays                |                  |
 //! | at 08 AM               | on the first Monday          |                  |
 //! | at 08 PM               | on the 4th Friday            | in even weeks    |
@@ -73,6 +144,15 @@
 //! | at 1:50 PM             | on the 3rd Monday            |                  |
 //! | at 1 PM                | on the 4th Saturday          |                  |
 //! | at 6 PM                | on the last Monday           |                  |
+//! | at 6 PM                | on the fifth Friday          |                  |
+//! | at 6 PM                | on the second-to-last Thursday |                |
+//! | at 7 AM                | on weekdays                  |                  |
+//! | at 7 AM                | on Monday through Thursday   | in even weeks    |
+//! | at 10 AM               | (Sat-Sun)                    |                  |
+//! | at 9 AM                | on the 1st and 15th of the month |              |
+//! | at 9 AM                | on the last day of the month |                  |
+//! | at 8 AM                | on business days             |                  |
+//! | at 6 PM                | on the first business day of the month |        |
 //!
 //! ## Ruleset
 //!
@@ -82,12 +162,18 @@
 //!
 //! ### Times specification
 //!
-//! * must start with _at_
-//! * then follows either _every full hour_ OR a list of distinct _times_
-//! * a _time_ adheres to the 12-hour-clock, so a number from 1 to 12 followed by _AM_ or _PM_ (uppercase!), e.g. 1 AM or 1 PM
-//! * a time may also contain _minutes_ from 00 to 59 (separated from the hour by a _colon_). Omitting the minutes means
-//! _on the hour_, e.g. 8 PM == 8:00 PM
+//! * must start with _at_ (which may be omitted before an interval)
+//! * then follows either _every full hour_, an _interval_ OR a list of distinct _times_
+//! * a _time_ adheres either to the 12-hour-clock, so a number from 1 to 12 followed by _AM_ or _PM_, e.g. 1 AM or 1 PM,
+//!   or to the 24-hour-clock, so a number from 0 to 23 followed by _minutes_, e.g. 13:00 or 18:30
+//! * a 12-hour time may also contain _minutes_ from 00 to 59 (separated from the hour by a _colon_). Omitting the minutes means
+//!   _on the hour_, e.g. 8 PM == 8:00 PM
+//! * a 24-hour time must always contain minutes, so a bare hour like _12_ is rejected as ambiguous
+//! * 12-hour and 24-hour times may be mixed in the same list
 //! * distinct times are concatenated by _commata_ or _and_
+//! * an _interval_ starts with _every_ followed by a number and either _minutes_ or _hours_, e.g. every 15 minutes
+//! * an interval may be restricted to a window of the day, e.g. every 2 hours between 8 AM and 6 PM. Both ends of the
+//!   window are included. Without a window the interval starts at midnight and spans the whole day.
 //!
 //! ### Weekday specification
 //!
@@ -95,15 +181,94 @@
 //! * succeeds the _time spec_
 //! * consists of a list of _weekdays_ with optional _modifiers_ to select only specific weekdays in a month.
 //! * the list either starts with _on_ OR is enclosed by simple braces _()_ for compactness
-//! * a weekday must be one of [ Monday | Tuesday | Wednesday | Thursday | Friday | Saturday | Sunday ] appended with an ***s*** if e.g. _every_ Monday is to be included OR a weekday preceded by a modifier [ first | 1st | second | 2nd | third | 3rd | fourth | 4th | last ] in order to include only specific weekdays in a month.
+//! * a weekday must be one of [ Monday | Tuesday | Wednesday | Thursday | Friday | Saturday | Sunday ] appended with an ***s*** if e.g. _every_ Monday is to be included OR a weekday preceded by a modifier [ first | 1st | second | 2nd | third | 3rd | fourth | 4th | fifth | 5th | last ] in order to include only specific weekdays in a month.
+//! * the modifiers _second_, _third_ and _fourth_ (or _2nd_, _3rd_ and _4th_) may be followed by _to last_ or _-to-last_ in order to count from the end of the month, e.g. the second-to-last Thursday
+//! * months that do not contain a fifth occurrence of a weekday are skipped
+//! * the keywords _weekdays_ (Monday to Friday) and _weekends_ (Saturday and Sunday) may be used instead of listing the weekdays
+//! * a range of weekdays is written as e.g. _Monday through Friday_ or, abbreviated, _Mon-Fri_ and may wrap around the end of the week, e.g. _Friday through Monday_
+//!
+//! ### Day-of-month specification
+//!
+//! * is _optional_ and replaces the _weekday spec_
+//! * succeeds the _time spec_
+//! * starts with _on the_ and ends with _of the month_, e.g. on the 1st and 15th of the month
+//! * consists of a list of days of the month as ordinal numbers from _1st_ to _31st_ and/or _last day_, concatenated by _commata_ or _and_
+//! * days that do not exist in shorter months (e.g. the 31st in April) are skipped. Append _or the last day_ in order to
+//!   use the last day of such months instead, e.g. on the 31st of the month or the last day
+//!
+//! ### Business day specification
+//!
+//! * is _optional_ and replaces the _weekday spec_
+//! * succeeds the _time spec_
+//! * must be one of _on business days_, _on the first business day of the month_ OR _on the last business day of the month_
+//! * a business day is a weekday from Monday to Friday that is not a holiday in any of the calendars attached to the
+//!   iterator with `calendar()` or `holidays()`. Without any calendars every weekday is a business day
+//!
+//! ### Month specification
+//!
+//! * is _optional_
+//! * succeeds the _weekday spec_ or _day-of-month spec_ or, if both are omitted, the _time spec_
+//! * consists of either a list of months starting with _in_, e.g. in January, April and July, OR an inclusive
+//!   range of months, e.g. from March to October
+//! * a month must be one of [ January | February | March | April | May | June | July | August | September | October | November | December ]
+//! * a range may wrap around the end of the year, e.g. from November to February
+//! * weekday modifiers apply to the selected months, e.g. on the first Monday in January
 //!
 //! ### Week specification
 //!
 //! * is _optional_
-//! * must be one of _in even weeks_ / _in odd weeks_
+//! * succeeds any of the preceding specifications and does not require a _weekday spec_, e.g. at 6 AM in odd weeks
+//! * must be one of _in even weeks_ / _in odd weeks_, a list of ISO week numbers like _in week 52_ / _in weeks 10 to 20_
+//!   OR a cycle of weeks like _every 3rd week starting 2024-01-01_
+//! * week numbers range from 1 to 53, may be concatenated by _commata_ or _and_ and may contain inclusive ranges that
+//!   wrap around the end of the year, e.g. in weeks 1, 5 and 50 to 2
+//! * even and odd weeks refer to the ISO week number. Note that in years with 53 ISO weeks both the last week of the year
+//!   and the first week of the following year are odd
+//! * a cycle of weeks consists of an ordinal number (or _other_ for every 2nd week) and an anchor date formatted as
+//!   _YYYY-MM-DD_. The week that contains the anchor date is the first week of the cycle, and the cycle also extends
+//!   to weeks before the anchor date
+//!
+//! ### Roll specification
+//!
+//! * is _optional_
+//! * succeeds any of the preceding specifications
+//! * must be one of _or the following business day_, _or the preceding business day_ OR
+//!   _or the modified following business day_
+//! * dates that do not fall on a business day are moved to the next or previous business day respectively, keeping
+//!   the time of day. The modified following business day is the next business day unless it falls into the next
+//!   month, in which case the previous business day is used
+//! * dates that are moved onto another date of the schedule are returned only once
+//! * a roll policy may also be set on the iterator with `roll()`, which applies to expressions without a roll spec
+//!
+//! ### Exception specification
+//!
+//! * is _optional_
+//! * succeeds all other specifications and starts with _except on_
+//! * consists of a list of exceptions concatenated by _commata_ or _and_, e.g. except on Sundays and December 25
+//! * an exception is either a date formatted as _YYYY-MM-DD_, a day of the year like _December 25_, weekdays in plural
+//!   like _Fridays_ (or the keywords _weekdays_ and _weekends_) OR the name of a list of holidays
+//! * dates and days of the year may form inclusive ranges, e.g. 2024-07-01 to 2024-07-14 or December 24 to January 6.
+//!   Ranges of days of the year may wrap around the end of the year
+//! * the name of a list of holidays consists of lowercase words and ends with _holidays_, e.g. public holidays. A
+//!   calendar of that name must be attached to the iterator with `calendar()` or `holidays()`, otherwise the iterator
+//!   returns an `Error::UnknownCalendar`. See the `calendar` module for loading lists of holidays from files
+//! * an excluded day is skipped entirely and the iterator returns the next date that is not excluded instead.
+//!   Exceptions refer to the dates of the schedule before they are moved by a roll spec
+//!
+//! ### Zone specification
+//!
+//! * is _optional_
+//! * succeeds all other specifications, separated by a single whitespace
+//! * must be either _UTC_, a fixed offset formatted as _+HH:MM_ or _-HH:MM_, e.g. +02:00, OR the name of a time zone
+//!   from the tz database, e.g. Europe/London
+//! * time zone names require the `tz` feature, otherwise parsing fails with an `Error::UnknownTimeZone`
+//! * a zone that is set on the iterator with `assume_offset()` or `time_zone()` takes precedence
+pub mod calendar;
 pub mod error;
 mod parse;
 pub mod schedule;
+#[cfg(feature = "tz")]
+pub mod timezone;
 mod types;
 
 pub use self::schedule::Schedule;
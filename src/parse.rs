@@ -1,6 +1,12 @@
+use crate::calendar::RollPolicy;
 use crate::error::*;
-use crate::types::{ParsedSchedule, WeekVariant, WeekdayModifier};
-use time::{Time, Weekday};
+#[cfg(feature = "tz")]
+use crate::timezone::TimeZone;
+use crate::types::{
+    BusinessDays, Exception, MonthDay, MonthDays, ParsedSchedule, TimeSpec, WeekVariant,
+    WeekdayModifier, Zone,
+};
+use time::{Date, Duration, Month, Time, UtcOffset, Weekday};
 
 // Prepares a format description for times formatted as e.g. "1 AM" or "01 AM".
 const TIME_FORMAT_NO_MINUTES: &[time::format_description::FormatItem] =
@@ -10,6 +16,18 @@ const TIME_FORMAT_NO_MINUTES: &[time::format_description::FormatItem] =
 const TIME_FORMAT_WITH_MINUTES: &[time::format_description::FormatItem] =
     time::macros::format_description!("[hour padding:none repr:12]:[minute] [period case:upper]");
 
+// Prepares a format description for times formatted as e.g. "8:00" or "18:30".
+const TIME_FORMAT_24_HOURS: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[hour padding:none repr:24]:[minute]");
+
+// Prepares a format description for dates formatted as e.g. "2024-01-01".
+const DATE_FORMAT: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[year]-[month]-[day]");
+
+// Prepares a format description for offsets formatted as e.g. "+02:00".
+const OFFSET_FORMAT: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+
 // Parses an expression block by block which are concatenated by "plus", checking for
 // possibly reaching the end of the expression along the way.
 // Returns a collection of parsed blocks.
@@ -21,29 +39,128 @@ pub(crate) fn parse(expression: &str) -> Result<ParsedSchedule, Error> {
         return Err(Error::EmptyExpression);
     }
 
-    eat_keyword("at", &mut position, &chars)?;
-    eat_whitespace(&mut position, &chars)?;
+    // A zone may be appended to the expression, e.g. "at 9 AM Europe/London".
+    // It is split off first, so that the remaining blocks are parsed as usual.
+    let (chars, zone) = split_zone(chars)?;
+
+    // Interval times read more naturally without the leading "at", e.g.
+    // "every 15 minutes between 9 AM and 5 PM", so it may be omitted here.
+    if !expect_sequence("every ", &position, &chars) {
+        eat_keyword("at", &mut position, &chars)?;
+        eat_whitespace(&mut position, &chars)?;
+    }
 
     let times = match_times(&mut position, &chars)?;
 
-    let days = if position < chars.len() {
-        Some(match_weekdays(&mut position, &chars)?)
+    let mut days = None;
+    let mut monthdays = None;
+    let mut business_days = None;
+
+    if position < chars.len()
+        && !expect_months(&position, &chars)
+        && !expect_week(&position, &chars)
+        && !expect_roll(&position, &chars)
+        && !expect_exceptions(&position, &chars)
+    {
+        if expect_business_days(&position, &chars) {
+            business_days = Some(match_business_days(&mut position, &chars)?);
+        } else if expect_monthdays(&position, &chars) {
+            monthdays = Some(match_monthdays(&mut position, &chars)?);
+        } else {
+            days = Some(match_weekdays(&mut position, &chars)?);
+        }
+    }
+
+    let months = if expect_months(&position, &chars) {
+        Some(match_months(&mut position, &chars)?)
     } else {
         None
     };
 
-    let weeks = if position < chars.len() {
+    let weeks = if position < chars.len()
+        && !expect_roll(&position, &chars)
+        && !expect_exceptions(&position, &chars)
+    {
         eat_whitespace(&mut position, &chars)?;
         Some(match_week(&mut position, &chars)?)
     } else {
         None
     };
 
-    let spec = ParsedSchedule { times, days, weeks };
+    let roll = if expect_roll(&position, &chars) {
+        Some(match_roll(&mut position, &chars)?)
+    } else {
+        None
+    };
+
+    let exceptions = if position < chars.len() {
+        eat_whitespace(&mut position, &chars)?;
+        Some(match_exceptions(&mut position, &chars)?)
+    } else {
+        None
+    };
+
+    let spec = ParsedSchedule {
+        times,
+        days,
+        monthdays,
+        business_days,
+        months,
+        weeks,
+        roll,
+        exceptions,
+        zone,
+    };
 
     Ok(spec)
 }
 
+// Splits off a trailing zone, which is either "UTC", a fixed offset like
+// "+02:00" or the name of a time zone like "Europe/London". Returns the
+// remaining expression and the parsed zone, if any.
+fn split_zone(chars: Vec<char>) -> Result<(Vec<char>, Option<Zone>), Error> {
+    let start = match chars.iter().rposition(|c| *c == ' ') {
+        Some(p) => p + 1,
+        None => return Ok((chars, None)),
+    };
+
+    let token: String = chars[start..].iter().collect();
+
+    let zone = if token == "UTC" {
+        Zone::Offset(UtcOffset::UTC)
+    } else if token.starts_with('+') || token.starts_with('-') {
+        let offset = UtcOffset::parse(&token, OFFSET_FORMAT).map_err(|_| {
+            Error::Syntax(SyntaxError {
+                position: start,
+                expected: "an offset like '+02:00'".to_string(),
+                continues: token.clone(),
+            })
+        })?;
+        Zone::Offset(offset)
+    } else if token.contains('/') && token.starts_with(|c: char| c.is_ascii_uppercase()) {
+        match_time_zone(&token)?
+    } else {
+        return Ok((chars, None));
+    };
+
+    let mut chars = chars;
+    chars.truncate(start - 1);
+
+    Ok((chars, Some(zone)))
+}
+
+// Loads a time zone from the tz database by its name.
+#[cfg(feature = "tz")]
+fn match_time_zone(name: &str) -> Result<Zone, Error> {
+    Ok(Zone::TimeZone(TimeZone::named(name)?))
+}
+
+// Time zones from the tz database require the "tz" feature.
+#[cfg(not(feature = "tz"))]
+fn match_time_zone(name: &str) -> Result<Zone, Error> {
+    Err(Error::UnknownTimeZone(name.to_string()))
+}
+
 // Looks ahead and checks for an arbitrary pattern without actually advancing the
 // pointer.
 fn expect_sequence(sequence: &str, position: &usize, chars: &[char]) -> bool {
@@ -97,28 +214,36 @@ fn eat_modifier(position: &mut usize, chars: &[char]) -> Result<WeekdayModifier,
         return Ok(WeekdayModifier::First);
     }
 
-    if eat_keyword("2nd", position, chars).is_ok() {
-        return Ok(WeekdayModifier::Second);
-    }
-
-    if eat_keyword("second", position, chars).is_ok() {
+    if eat_keyword("2nd", position, chars).is_ok() || eat_keyword("second", position, chars).is_ok()
+    {
+        if eat_to_last(position, chars) {
+            return Ok(WeekdayModifier::SecondToLast);
+        }
         return Ok(WeekdayModifier::Second);
     }
 
-    if eat_keyword("3rd", position, chars).is_ok() {
+    if eat_keyword("3rd", position, chars).is_ok() || eat_keyword("third", position, chars).is_ok()
+    {
+        if eat_to_last(position, chars) {
+            return Ok(WeekdayModifier::ThirdToLast);
+        }
         return Ok(WeekdayModifier::Third);
     }
 
-    if eat_keyword("third", position, chars).is_ok() {
-        return Ok(WeekdayModifier::Third);
+    if eat_keyword("4th", position, chars).is_ok() || eat_keyword("fourth", position, chars).is_ok()
+    {
+        if eat_to_last(position, chars) {
+            return Ok(WeekdayModifier::FourthToLast);
+        }
+        return Ok(WeekdayModifier::Fourth);
     }
 
-    if eat_keyword("4th", position, chars).is_ok() {
-        return Ok(WeekdayModifier::Fourth);
+    if eat_keyword("5th", position, chars).is_ok() {
+        return Ok(WeekdayModifier::Fifth);
     }
 
-    if eat_keyword("fourth", position, chars).is_ok() {
-        return Ok(WeekdayModifier::Fourth);
+    if eat_keyword("fifth", position, chars).is_ok() {
+        return Ok(WeekdayModifier::Fifth);
     }
 
     if eat_keyword("last", position, chars).is_ok() {
@@ -128,7 +253,7 @@ fn eat_modifier(position: &mut usize, chars: &[char]) -> Result<WeekdayModifier,
     let err = SyntaxError {
         position: *position,
         expected:
-            "one of '1st', 'first', '2nd', 'second', '3rd', 'third', '4th', 'fourth' or 'last'"
+            "one of '1st', 'first', '2nd', 'second', '3rd', 'third', '4th', 'fourth', '5th', 'fifth' or 'last'"
                 .to_string(),
         continues: chars
             .get(*position..*position + 10)
@@ -141,6 +266,14 @@ fn eat_modifier(position: &mut usize, chars: &[char]) -> Result<WeekdayModifier,
     Err(Error::Syntax(err))
 }
 
+// Consumes the suffix that turns an ordinal modifier into an offset from the
+// end of the month, i.e. either " to last" or "-to-last". Returns whether the
+// suffix was present.
+fn eat_to_last(position: &mut usize, chars: &[char]) -> bool {
+    eat_keyword(" to last", position, chars).is_ok()
+        || eat_keyword("-to-last", position, chars).is_ok()
+}
+
 // Consumes a well-defined weekday (either worded in a specific or "general" way).
 // Returns either the parsed representation of and error if the pattern at hand
 // does not match the expected string or no more characters are there to consume.
@@ -198,7 +331,7 @@ fn eat_weekday(position: &mut usize, chars: &[char], specific: bool) -> Result<W
         }
     }
 
-    return Ok(day);
+    Ok(day)
 }
 
 // Consumes a single whitespace or returns an error.
@@ -223,64 +356,89 @@ fn eat_whitespace(position: &mut usize, chars: &[char]) -> Result<(), Error> {
     }
 }
 
-// Matches, parses and returns a collection of parsed times.
-fn match_times(position: &mut usize, chars: &[char]) -> Result<Vec<Time>, Error> {
+// Matches, parses and returns either the "every full hour" keyword or a
+// collection of parsed times.
+fn match_times(position: &mut usize, chars: &[char]) -> Result<TimeSpec, Error> {
+    if eat_keyword("every full hour", position, chars).is_ok() {
+        return Ok(TimeSpec::EveryFullHour);
+    }
+
+    if eat_keyword("every", position, chars).is_ok() {
+        eat_whitespace(position, chars)?;
+        return match_interval(position, chars);
+    }
+
     let mut tokens = vec![];
 
     tokens.push(match_time(position, chars)?);
 
     // Check for more occurrences of time tokens.
-    loop {
-        match chars.get(*position) {
-            Some(ch) => {
-                if *ch == ',' {
-                    *position += 1;
-                    eat_whitespace(position, chars)?;
-                    tokens.push(match_time(position, chars)?);
-                    continue;
-                } else if ch.is_whitespace() {
-                    if expect_sequence(" and", &position, &chars) {
-                        eat_whitespace(position, chars)?;
-                        eat_keyword("and", position, chars)?;
-                        eat_whitespace(position, chars)?;
-                        tokens.push(match_time(position, chars)?);
-                        continue;
-                    } else {
-                        break;
-                    }
-                } else {
-                    let err = SyntaxError {
-                        position: *position,
-                        expected: "either ',' or a whitespace".to_string(),
-                        continues: chars
-                            .get(*position..*position + 10)
-                            .or(chars.get(*position..))
-                            .unwrap()
-                            .iter()
-                            .collect::<String>(),
-                    };
-                    return Err(Error::Syntax(err));
-                }
+    while let Some(ch) = chars.get(*position) {
+        if *ch == ',' {
+            *position += 1;
+            eat_whitespace(position, chars)?;
+            tokens.push(match_time(position, chars)?);
+            continue;
+        } else if ch.is_whitespace() {
+            if expect_sequence(" and", position, chars) {
+                eat_whitespace(position, chars)?;
+                eat_keyword("and", position, chars)?;
+                eat_whitespace(position, chars)?;
+                tokens.push(match_time(position, chars)?);
+                continue;
+            } else {
+                break;
             }
-            None => break,
+        } else {
+            let err = SyntaxError {
+                position: *position,
+                expected: "either ',' or a whitespace".to_string(),
+                continues: chars
+                    .get(*position..*position + 10)
+                    .or(chars.get(*position..))
+                    .unwrap()
+                    .iter()
+                    .collect::<String>(),
+            };
+            return Err(Error::Syntax(err));
         }
     }
 
-    Ok(tokens)
+    Ok(TimeSpec::List(tokens))
 }
 
-// Matches and parses a single time.
-fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
-    // First character must be a number.
-    let hour = chars
-        .get(*position)
-        .ok_or(Error::UnexpectedEndOfInput)?
-        .clone();
+// Matches and parses an interval of times that starts with a step
+// like "15 minutes" or "2 hours" and is optionally followed by a
+// window like "between 9 AM and 5 PM". Without a window the interval
+// spans the whole day.
+fn match_interval(position: &mut usize, chars: &[char]) -> Result<TimeSpec, Error> {
+    let start_pos = *position;
+    let amount = eat_number(position, chars)?;
 
-    if !hour.is_numeric() {
+    if amount == 0 {
+        let err = SyntaxError {
+            position: start_pos,
+            expected: "a number greater than zero".to_string(),
+            continues: chars
+                .get(start_pos..start_pos + 10)
+                .or(chars.get(start_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    eat_whitespace(position, chars)?;
+
+    let step = if eat_keyword("minutes", position, chars).is_ok() {
+        Duration::minutes(amount as i64)
+    } else if eat_keyword("hours", position, chars).is_ok() {
+        Duration::hours(amount as i64)
+    } else {
         let err = SyntaxError {
             position: *position,
-            expected: "a number in the range 01..=12".to_string(),
+            expected: "either 'minutes' or 'hours'".to_string(),
             continues: chars
                 .get(*position..*position + 10)
                 .or(chars.get(*position..))
@@ -289,80 +447,162 @@ fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
                 .collect::<String>(),
         };
         return Err(Error::Syntax(err));
+    };
+
+    if !expect_sequence(" between", position, chars) {
+        return Ok(TimeSpec::Interval {
+            step,
+            start: Time::MIDNIGHT,
+            end: Time::from_hms(23, 59, 0).unwrap(),
+        });
     }
 
-    *position += 1;
+    eat_whitespace(position, chars)?;
+    eat_keyword("between", position, chars)?;
+    eat_whitespace(position, chars)?;
 
-    // Next character may be the next part of a 2-digit number, a colon,
-    // or a whitespace.
-    let next = chars.get(*position).ok_or(Error::UnexpectedEndOfInput)?;
+    let start = match_time(position, chars)?;
 
-    if next.is_whitespace() {
-        *position += 1;
-        let mut time = String::new();
-        time.push(hour);
-        time.push(' ');
+    eat_whitespace(position, chars)?;
+    eat_keyword("and", position, chars)?;
+    eat_whitespace(position, chars)?;
 
-        for i in 0..2 {
-            time.push(match_period(i, position, chars)?);
-        }
+    let end_pos = *position;
+    let end = match_time(position, chars)?;
+
+    if end < start {
+        let err = SyntaxError {
+            position: end_pos,
+            expected: "a time that does not precede the start of the interval".to_string(),
+            continues: chars
+                .get(end_pos..end_pos + 10)
+                .or(chars.get(end_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
 
-        *position += 2;
+    Ok(TimeSpec::Interval { step, start, end })
+}
 
-        let parsed = Time::parse(&time, &TIME_FORMAT_NO_MINUTES).map_err(Error::TimeParse)?;
+// Consumes a sequence of digits and returns the parsed number or an error
+// if the pattern at hand does not start with a digit.
+fn eat_number(position: &mut usize, chars: &[char]) -> Result<u32, Error> {
+    let start_pos = *position;
 
-        Ok(parsed)
-    } else if *next == ':' {
-        *position += 1;
-        let mut time = String::new();
-        time.push(hour);
-        time.push(*next);
+    while let Some(c) = chars.get(*position) {
+        if c.is_ascii_digit() {
+            *position += 1;
+        } else {
+            break;
+        }
+    }
+
+    let digits: String = chars[start_pos..*position].iter().collect();
 
-        for i in 0..2 {
-            time.push(match_minute(i, position, chars)?);
+    match digits.parse::<u32>() {
+        Ok(number) => Ok(number),
+        Err(_) if start_pos >= chars.len() => Err(Error::UnexpectedEndOfInput),
+        Err(_) => {
+            let err = SyntaxError {
+                position: start_pos,
+                expected: "a number".to_string(),
+                continues: chars
+                    .get(start_pos..start_pos + 10)
+                    .or(chars.get(start_pos..))
+                    .unwrap()
+                    .iter()
+                    .collect::<String>(),
+            };
+            Err(Error::Syntax(err))
         }
+    }
+}
 
-        *position += 2;
+// Consumes an ordinal number like "1st", "2nd" or "15th" and returns the
+// parsed number or an error if the number is not followed by the correct
+// suffix.
+fn eat_ordinal(position: &mut usize, chars: &[char]) -> Result<u32, Error> {
+    let number = eat_number(position, chars)?;
+
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
 
-        eat_whitespace(position, chars)?;
-        time.push(' ');
+    eat_keyword(suffix, position, chars)?;
 
-        for i in 0..2 {
-            time.push(match_period(i, position, chars)?);
-        }
+    Ok(number)
+}
 
-        *position += 2;
+// Consumes a date formatted as e.g. "2024-01-01" and returns the parsed
+// representation of it.
+fn eat_date(position: &mut usize, chars: &[char]) -> Result<Date, Error> {
+    let end_pos = *position + 10;
 
-        let parsed = Time::parse(&time, &TIME_FORMAT_WITH_MINUTES).map_err(Error::TimeParse)?;
+    let date: String = chars
+        .get(*position..end_pos)
+        .ok_or(Error::UnexpectedEndOfInput)?
+        .iter()
+        .collect();
 
-        Ok(parsed)
-    } else if next.is_numeric() {
-        *position += 1;
-        let mut time = String::new();
-        time.push(hour);
-        time.push(*next);
+    let parsed = Date::parse(&date, &DATE_FORMAT).map_err(Error::TimeParse)?;
 
-        let next = chars
-            .get(*position)
-            .ok_or(Error::UnexpectedEndOfInput)?
-            .clone();
+    *position = end_pos;
 
-        *position += 1;
+    Ok(parsed)
+}
 
-        if next.is_whitespace() {
-            time.push(' ');
+// Matches and parses a single time, either on the 12-hour clock with a
+// trailing period (e.g. "6 PM" or "6:30 PM") or on the 24-hour clock with
+// mandatory minutes (e.g. "18:30"). A bare hour without period or minutes
+// is ambiguous and rejected.
+fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
+    let start_pos = *position;
 
-            for i in 0..2 {
-                time.push(match_period(i, position, chars)?);
-            }
+    // First character must be a number.
+    let hour = chars
+        .get(*position)
+        .copied()
+        .ok_or(Error::UnexpectedEndOfInput)?;
 
-            *position += 2;
+    if !hour.is_numeric() {
+        let err = SyntaxError {
+            position: *position,
+            expected: "a number in the range 01..=12 or 00..=23".to_string(),
+            continues: chars
+                .get(*position..*position + 10)
+                .or(chars.get(*position..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    *position += 1;
+
+    let mut time = String::new();
+    time.push(hour);
 
-            let parsed = Time::parse(&time, &TIME_FORMAT_NO_MINUTES).map_err(Error::TimeParse)?;
+    // Next character may be the next part of a 2-digit number, a colon,
+    // or a whitespace.
+    if let Some(next) = chars.get(*position) {
+        if next.is_numeric() {
+            time.push(*next);
+            *position += 1;
+        }
+    }
 
-            Ok(parsed)
-        } else if next == ':' {
-            time.push(next);
+    match chars.get(*position) {
+        Some(':') => {
+            time.push(':');
+            *position += 1;
 
             for i in 0..2 {
                 time.push(match_minute(i, position, chars)?);
@@ -370,6 +610,22 @@ fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
 
             *position += 2;
 
+            if expect_period(position, chars) {
+                eat_whitespace(position, chars)?;
+                time.push(' ');
+
+                for i in 0..2 {
+                    time.push(match_period(i, position, chars)?);
+                }
+
+                *position += 2;
+
+                Time::parse(&time, &TIME_FORMAT_WITH_MINUTES).map_err(Error::TimeParse)
+            } else {
+                Time::parse(&time, &TIME_FORMAT_24_HOURS).map_err(Error::TimeParse)
+            }
+        }
+        Some(c) if c.is_whitespace() && expect_period(position, chars) => {
             eat_whitespace(position, chars)?;
             time.push(' ');
 
@@ -379,13 +635,13 @@ fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
 
             *position += 2;
 
-            let parsed = Time::parse(&time, &TIME_FORMAT_WITH_MINUTES).map_err(Error::TimeParse)?;
-
-            Ok(parsed)
-        } else {
+            Time::parse(&time, &TIME_FORMAT_NO_MINUTES).map_err(Error::TimeParse)
+        }
+        Some(c) if !c.is_whitespace() => {
             let err = SyntaxError {
                 position: *position,
-                expected: "either ',' or a whitespace".to_string(),
+                expected: "the remainder of a number in the range 01..=12 or 00..=23, a colon or a whitespace"
+                    .to_string(),
                 continues: chars
                     .get(*position..*position + 10)
                     .or(chars.get(*position..))
@@ -395,22 +651,32 @@ fn match_time(position: &mut usize, chars: &[char]) -> Result<Time, Error> {
             };
             Err(Error::Syntax(err))
         }
-    } else {
-        let err = SyntaxError {
-            position: *position,
-            expected: "the remainder of a number in the range 01..=12, a colon or a whitespace"
-                .to_string(),
-            continues: chars
-                .get(*position..*position + 10)
-                .or(chars.get(*position..))
-                .unwrap()
-                .iter()
-                .collect::<String>(),
-        };
-        Err(Error::Syntax(err))
+        _ => {
+            let err = SyntaxError {
+                position: start_pos,
+                expected: "either a 12-hour time with 'AM' or 'PM' (e.g. '6 PM') or a 24-hour time with minutes (e.g. '18:00')"
+                    .to_string(),
+                continues: chars
+                    .get(start_pos..start_pos + 10)
+                    .or(chars.get(start_pos..))
+                    .unwrap()
+                    .iter()
+                    .collect::<String>(),
+            };
+            Err(Error::Syntax(err))
+        }
     }
 }
 
+// Looks ahead and checks if the next characters are a whitespace followed
+// by a period of the 12-hour clock, i.e. either "AM" or "PM" in upper- or
+// lowercase.
+fn expect_period(position: &usize, chars: &[char]) -> bool {
+    [" AM", " PM", " am", " pm"]
+        .iter()
+        .any(|p| expect_sequence(p, position, chars))
+}
+
 // Match and check the next character as part of a double-digit time period
 // on a surface level (heavy lifting is done by the time crate).
 // In order to provide a more useful error message this function only checks one
@@ -422,12 +688,12 @@ fn match_period(index: usize, position: &mut usize, chars: &[char]) -> Result<ch
         .get(*position + index)
         .ok_or(Error::UnexpectedEndOfInput)?;
 
-    if c.is_alphabetic() && c.is_uppercase() {
-        Ok(*c)
+    if c.is_alphabetic() {
+        Ok(c.to_ascii_uppercase())
     } else {
         let err = SyntaxError {
             position: *position + index,
-            expected: "either 'AM or 'PM'".to_string(),
+            expected: "either 'AM' or 'PM'".to_string(),
             continues: chars
                 .get(*position..*position + 10)
                 .or(chars.get(*position..))
@@ -435,7 +701,7 @@ fn match_period(index: usize, position: &mut usize, chars: &[char]) -> Result<ch
                 .iter()
                 .collect::<String>(),
         };
-        return Err(Error::Syntax(err));
+        Err(Error::Syntax(err))
     }
 }
 
@@ -463,7 +729,7 @@ fn match_minute(index: usize, position: &mut usize, chars: &[char]) -> Result<ch
                 .iter()
                 .collect::<String>(),
         };
-        return Err(Error::Syntax(err));
+        Err(Error::Syntax(err))
     }
 }
 
@@ -493,20 +759,20 @@ fn match_weekdays(
         }
     };
 
-    tokens.push(match_weekday(position, chars)?);
+    tokens.append(&mut match_weekday(position, chars)?);
 
     while let Some(ch) = chars.get(*position) {
         if *ch == ',' {
             *position += 1;
             eat_whitespace(position, chars)?;
-            tokens.push(match_weekday(position, chars)?);
+            tokens.append(&mut match_weekday(position, chars)?);
             continue;
         } else if ch.is_whitespace() {
-            if expect_sequence(" and", &position, &chars) {
+            if expect_sequence(" and", position, chars) {
                 eat_whitespace(position, chars)?;
                 eat_keyword("and", position, chars)?;
                 eat_whitespace(position, chars)?;
-                tokens.push(match_weekday(position, chars)?);
+                tokens.append(&mut match_weekday(position, chars)?);
                 continue;
             } else {
                 if has_braces {
@@ -575,15 +841,25 @@ fn match_weekdays(
     Ok(tokens)
 }
 
-// Matches and parses a single weekday with optional modifier.
+// Matches and parses a single weekday with optional modifier. The keywords
+// "weekdays" and "weekends" as well as ranges like "Monday through Friday" or
+// "Mon-Fri" are expanded into the respective collection of weekdays.
 fn match_weekday(
     position: &mut usize,
     chars: &[char],
-) -> Result<(Weekday, Option<WeekdayModifier>), Error> {
+) -> Result<Vec<(Weekday, Option<WeekdayModifier>)>, Error> {
     let next = chars
         .get(*position)
-        .ok_or(Error::UnexpectedEndOfInput)?
-        .clone();
+        .copied()
+        .ok_or(Error::UnexpectedEndOfInput)?;
+
+    if eat_keyword("weekdays", position, chars).is_ok() {
+        return Ok(expand_weekdays(Weekday::Monday, Weekday::Friday));
+    }
+
+    if eat_keyword("weekends", position, chars).is_ok() {
+        return Ok(expand_weekdays(Weekday::Saturday, Weekday::Sunday));
+    }
 
     let mut modifier = None;
 
@@ -598,29 +874,389 @@ fn match_weekday(
         eat_whitespace(position, chars)?;
     }
 
+    if modifier.is_none() {
+        let start_pos = *position;
+
+        if let Ok(first) = eat_weekday_name(position, chars) {
+            if eat_keyword("-", position, chars).is_ok() {
+                let last = eat_weekday_name(position, chars)?;
+                return Ok(expand_weekdays(first, last));
+            }
+
+            if expect_sequence(" through ", position, chars) {
+                eat_whitespace(position, chars)?;
+                eat_keyword("through", position, chars)?;
+                eat_whitespace(position, chars)?;
+                let last = eat_weekday_name(position, chars)?;
+                return Ok(expand_weekdays(first, last));
+            }
+        }
+
+        // Not a range, so rewind and expect a single weekday in plural.
+        *position = start_pos;
+    }
+
     let day = if modifier.is_some() {
         eat_weekday(position, chars, true)?
     } else {
         eat_weekday(position, chars, false)?
     };
 
-    return Ok((day, modifier));
+    Ok(vec![(day, modifier)])
 }
 
-// Matches and parses the week modifier.
-fn match_week(position: &mut usize, chars: &[char]) -> Result<WeekVariant, Error> {
+// Consumes the name of a weekday as part of a range, either in full (e.g.
+// "Monday") or abbreviated (e.g. "Mon").
+fn eat_weekday_name(position: &mut usize, chars: &[char]) -> Result<Weekday, Error> {
+    let days = [
+        ("Monday", Weekday::Monday),
+        ("Tuesday", Weekday::Tuesday),
+        ("Wednesday", Weekday::Wednesday),
+        ("Thursday", Weekday::Thursday),
+        ("Friday", Weekday::Friday),
+        ("Saturday", Weekday::Saturday),
+        ("Sunday", Weekday::Sunday),
+    ];
+
+    for (name, day) in days {
+        if eat_keyword(name, position, chars).is_ok() {
+            return Ok(day);
+        }
+    }
+
+    for (name, day) in days {
+        if eat_keyword(&name[..3], position, chars).is_ok() {
+            return Ok(day);
+        }
+    }
+
     if *position >= chars.len() {
         return Err(Error::UnexpectedEndOfInput);
     }
 
-    if eat_keyword("in even weeks", position, chars).is_ok() {
-        return Ok(WeekVariant::Even);
-    } else if eat_keyword("in odd weeks", position, chars).is_ok() {
-        return Ok(WeekVariant::Odd);
-    } else {
-        let err = SyntaxError {
-            position: *position,
-            expected: "either 'in even weeks' or 'in odd weeks'".to_string(),
+    let err = SyntaxError {
+        position: *position,
+        expected: "the name of a weekday, e.g. 'Monday' or 'Mon'".to_string(),
+        continues: chars
+            .get(*position..*position + 10)
+            .or(chars.get(*position..))
+            .unwrap()
+            .iter()
+            .collect::<String>(),
+    };
+
+    Err(Error::Syntax(err))
+}
+
+// Expands an inclusive range of weekdays, wrapping around the end of the
+// week if necessary, e.g. Friday through Monday.
+fn expand_weekdays(first: Weekday, last: Weekday) -> Vec<(Weekday, Option<WeekdayModifier>)> {
+    let mut tokens = vec![(first, None)];
+    let mut day = first;
+
+    while day != last {
+        day = day.next();
+        tokens.push((day, None));
+    }
+
+    tokens
+}
+
+// Looks ahead and checks if the upcoming block is a day-of-month specification
+// like "on the 15th of the month" rather than a weekday specification like
+// "on the 1st Monday", without actually advancing the pointer.
+fn expect_monthdays(position: &usize, chars: &[char]) -> bool {
+    if !expect_sequence(" on the ", position, chars) {
+        return false;
+    }
+
+    let mut pos = *position + 8;
+
+    if expect_sequence("last day", &pos, chars) {
+        return true;
+    }
+
+    let start_pos = pos;
+
+    while chars.get(pos).map_or(false, |c| c.is_ascii_digit()) {
+        pos += 1;
+    }
+
+    if pos == start_pos {
+        return false;
+    }
+
+    // Skip the ordinal suffix, then a weekday would follow in a weekday
+    // specification, while anything else belongs to a day-of-month list.
+    pos += 2;
+
+    !expect_sequence(" ", &pos, chars)
+        || [" and", " of", " or"]
+            .iter()
+            .any(|s| expect_sequence(s, &pos, chars))
+}
+
+// Matches, parses and returns a collection of days of the month.
+fn match_monthdays(position: &mut usize, chars: &[char]) -> Result<MonthDays, Error> {
+    let mut tokens = vec![];
+
+    eat_whitespace(position, chars)?;
+    eat_keyword("on", position, chars)?;
+    eat_whitespace(position, chars)?;
+    eat_keyword("the", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    tokens.push(match_monthday(position, chars)?);
+
+    loop {
+        if expect_sequence(",", position, chars) {
+            *position += 1;
+            eat_whitespace(position, chars)?;
+        } else if expect_sequence(" and", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("and", position, chars)?;
+            eat_whitespace(position, chars)?;
+        } else {
+            break;
+        }
+
+        if eat_keyword("the", position, chars).is_ok() {
+            eat_whitespace(position, chars)?;
+        }
+
+        tokens.push(match_monthday(position, chars)?);
+    }
+
+    eat_whitespace(position, chars)?;
+    eat_keyword("of the month", position, chars)?;
+
+    // Days that do not exist in shorter months are skipped unless the user
+    // explicitly asks to fall back to the last day of such months.
+    let clamp = if expect_sequence(" or the last day", position, chars) {
+        eat_whitespace(position, chars)?;
+        eat_keyword("or the last day", position, chars)?;
+        true
+    } else {
+        false
+    };
+
+    Ok(MonthDays {
+        days: tokens,
+        clamp,
+    })
+}
+
+// Matches and parses a single day of the month, either as an ordinal number
+// like "15th" or as "last day".
+fn match_monthday(position: &mut usize, chars: &[char]) -> Result<MonthDay, Error> {
+    if eat_keyword("last day", position, chars).is_ok() {
+        return Ok(MonthDay::Last);
+    }
+
+    let start_pos = *position;
+    let day = eat_ordinal(position, chars)?;
+
+    if !(1..=31).contains(&day) {
+        let err = SyntaxError {
+            position: start_pos,
+            expected: "a day of the month in the range 1st..=31st or 'last day'".to_string(),
+            continues: chars
+                .get(start_pos..start_pos + 10)
+                .or(chars.get(start_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    Ok(MonthDay::Day(day as u8))
+}
+
+// Looks ahead and checks if the upcoming block is a business day specification
+// like "on business days" or "on the last business day of the month", without
+// actually advancing the pointer.
+fn expect_business_days(position: &usize, chars: &[char]) -> bool {
+    [
+        " on business days",
+        " on the first business day",
+        " on the last business day",
+    ]
+    .iter()
+    .any(|s| expect_sequence(s, position, chars))
+}
+
+// Matches and parses a business day specification.
+fn match_business_days(position: &mut usize, chars: &[char]) -> Result<BusinessDays, Error> {
+    eat_whitespace(position, chars)?;
+    eat_keyword("on", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    if eat_keyword("business days", position, chars).is_ok() {
+        return Ok(BusinessDays::Every);
+    }
+
+    eat_keyword("the", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let business_days = if eat_keyword("first", position, chars).is_ok() {
+        BusinessDays::First
+    } else {
+        eat_keyword("last", position, chars)?;
+        BusinessDays::Last
+    };
+
+    eat_whitespace(position, chars)?;
+    eat_keyword("business day of the month", position, chars)?;
+
+    Ok(business_days)
+}
+
+// Looks ahead and checks if the upcoming block is a month specification
+// like "in January" or "from March to October", without actually advancing
+// the pointer.
+fn expect_months(position: &usize, chars: &[char]) -> bool {
+    if expect_sequence(" from ", position, chars) {
+        return true;
+    }
+
+    expect_sequence(" in ", position, chars)
+        && chars
+            .get(*position + 4)
+            .map_or(false, |c| c.is_alphabetic() && c.is_uppercase())
+}
+
+// Matches, parses and returns a collection of months, either as a list
+// like "in January, April and July" or as an inclusive range like "from
+// March to October". Ranges may wrap around the end of the year, e.g.
+// "from November to February".
+fn match_months(position: &mut usize, chars: &[char]) -> Result<Vec<Month>, Error> {
+    eat_whitespace(position, chars)?;
+
+    if eat_keyword("from", position, chars).is_ok() {
+        eat_whitespace(position, chars)?;
+        let first = eat_month(position, chars)?;
+        eat_whitespace(position, chars)?;
+        eat_keyword("to", position, chars)?;
+        eat_whitespace(position, chars)?;
+        let last = eat_month(position, chars)?;
+
+        let mut tokens = vec![first];
+        let mut month = first;
+
+        while month != last {
+            month = month.next();
+            tokens.push(month);
+        }
+
+        return Ok(tokens);
+    }
+
+    eat_keyword("in", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let mut tokens = vec![eat_month(position, chars)?];
+
+    loop {
+        if expect_sequence(",", position, chars) {
+            *position += 1;
+            eat_whitespace(position, chars)?;
+        } else if expect_sequence(" and", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("and", position, chars)?;
+            eat_whitespace(position, chars)?;
+        } else {
+            break;
+        }
+
+        tokens.push(eat_month(position, chars)?);
+    }
+
+    Ok(tokens)
+}
+
+// Consumes a well-defined month and returns the parsed representation of it
+// or an error if the pattern at hand does not match the expected string.
+fn eat_month(position: &mut usize, chars: &[char]) -> Result<Month, Error> {
+    let months = [
+        ("January", Month::January),
+        ("February", Month::February),
+        ("March", Month::March),
+        ("April", Month::April),
+        ("May", Month::May),
+        ("June", Month::June),
+        ("July", Month::July),
+        ("August", Month::August),
+        ("September", Month::September),
+        ("October", Month::October),
+        ("November", Month::November),
+        ("December", Month::December),
+    ];
+
+    for (name, month) in months {
+        if eat_keyword(name, position, chars).is_ok() {
+            return Ok(month);
+        }
+    }
+
+    if *position >= chars.len() {
+        return Err(Error::UnexpectedEndOfInput);
+    }
+
+    let err = SyntaxError {
+        position: *position,
+        expected: "the name of a month, e.g. 'January'".to_string(),
+        continues: chars
+            .get(*position..*position + 10)
+            .or(chars.get(*position..))
+            .unwrap()
+            .iter()
+            .collect::<String>(),
+    };
+
+    Err(Error::Syntax(err))
+}
+
+// Looks ahead and checks if the upcoming block is a week specification like
+// "in odd weeks" or "every 3rd week starting 2024-01-01", without actually
+// advancing the pointer.
+fn expect_week(position: &usize, chars: &[char]) -> bool {
+    if expect_sequence(" every ", position, chars) {
+        return true;
+    }
+
+    expect_sequence(" in ", position, chars)
+        && chars
+            .get(*position + 4)
+            .map_or(false, |c| c.is_alphabetic() && c.is_lowercase())
+}
+
+// Matches and parses the week modifier.
+fn match_week(position: &mut usize, chars: &[char]) -> Result<WeekVariant, Error> {
+    if *position >= chars.len() {
+        return Err(Error::UnexpectedEndOfInput);
+    }
+
+    if eat_keyword("in even weeks", position, chars).is_ok() {
+        Ok(WeekVariant::Even)
+    } else if eat_keyword("in odd weeks", position, chars).is_ok() {
+        Ok(WeekVariant::Odd)
+    } else if eat_keyword("in weeks", position, chars).is_ok() {
+        eat_whitespace(position, chars)?;
+        match_week_numbers(position, chars)
+    } else if eat_keyword("in week", position, chars).is_ok() {
+        eat_whitespace(position, chars)?;
+        Ok(WeekVariant::Numbers(vec![eat_week_number(
+            position, chars,
+        )?]))
+    } else if eat_keyword("every", position, chars).is_ok() {
+        eat_whitespace(position, chars)?;
+        match_week_cycle(position, chars)
+    } else {
+        let err = SyntaxError {
+            position: *position,
+            expected: "one of 'in even weeks', 'in odd weeks', 'in week', 'in weeks' or 'every'"
+                .to_string(),
             continues: chars
                 .get(*position..*position + 10)
                 .or(chars.get(*position..))
@@ -628,29 +1264,844 @@ fn match_week(position: &mut usize, chars: &[char]) -> Result<WeekVariant, Error
                 .iter()
                 .collect::<String>(),
         };
+        Err(Error::Syntax(err))
+    }
+}
+
+// Matches, parses and returns a sorted collection of ISO week numbers,
+// either listed (e.g. "1, 5 and 10") or as inclusive ranges (e.g. "10 to
+// 20"), or a mix of both. Ranges may wrap around the end of the year, e.g.
+// "50 to 2".
+fn match_week_numbers(position: &mut usize, chars: &[char]) -> Result<WeekVariant, Error> {
+    let mut tokens = vec![];
+
+    loop {
+        let first = eat_week_number(position, chars)?;
+
+        if expect_sequence(" to ", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("to", position, chars)?;
+            eat_whitespace(position, chars)?;
+
+            let last = eat_week_number(position, chars)?;
+            let mut week = first;
+
+            tokens.push(week);
+
+            while week != last {
+                week = if week == 53 { 1 } else { week + 1 };
+                tokens.push(week);
+            }
+        } else {
+            tokens.push(first);
+        }
+
+        if expect_sequence(",", position, chars) {
+            *position += 1;
+            eat_whitespace(position, chars)?;
+        } else if expect_sequence(" and", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("and", position, chars)?;
+            eat_whitespace(position, chars)?;
+        } else {
+            break;
+        }
+    }
+
+    tokens.sort_unstable();
+    tokens.dedup();
+
+    Ok(WeekVariant::Numbers(tokens))
+}
+
+// Consumes an ISO week number in the range 1..=53.
+fn eat_week_number(position: &mut usize, chars: &[char]) -> Result<u8, Error> {
+    let start_pos = *position;
+    let week = eat_number(position, chars)?;
+
+    if !(1..=53).contains(&week) {
+        let err = SyntaxError {
+            position: start_pos,
+            expected: "a week number in the range 1..=53".to_string(),
+            continues: chars
+                .get(start_pos..start_pos + 10)
+                .or(chars.get(start_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    Ok(week as u8)
+}
+
+// Matches and parses a cycle of weeks like "3rd week starting 2024-01-01"
+// or "other week starting 2024-01-01".
+fn match_week_cycle(position: &mut usize, chars: &[char]) -> Result<WeekVariant, Error> {
+    let start_pos = *position;
+
+    let interval = if eat_keyword("other", position, chars).is_ok() {
+        2
+    } else {
+        eat_ordinal(position, chars)?
+    };
+
+    if interval == 0 || interval > u16::MAX as u32 {
+        let err = SyntaxError {
+            position: start_pos,
+            expected: "an ordinal number greater than zero or 'other'".to_string(),
+            continues: chars
+                .get(start_pos..start_pos + 10)
+                .or(chars.get(start_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
         return Err(Error::Syntax(err));
     }
+
+    eat_whitespace(position, chars)?;
+    eat_keyword("week", position, chars)?;
+    eat_whitespace(position, chars)?;
+    eat_keyword("starting", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let anchor = eat_date(position, chars)?;
+
+    Ok(WeekVariant::Cycle {
+        interval: interval as u16,
+        anchor,
+    })
+}
+
+// Looks ahead and checks if the upcoming block is a roll specification like
+// "or the following business day", without actually advancing the pointer.
+fn expect_roll(position: &usize, chars: &[char]) -> bool {
+    expect_sequence(" or the ", position, chars)
+        && [
+            "following business day",
+            "preceding business day",
+            "modified following business day",
+        ]
+        .iter()
+        .any(|s| expect_sequence(s, &(*position + 8), chars))
+}
+
+// Matches and parses the policy that moves dates that do not fall on a
+// business day.
+fn match_roll(position: &mut usize, chars: &[char]) -> Result<RollPolicy, Error> {
+    eat_whitespace(position, chars)?;
+    eat_keyword("or the", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let policy = if eat_keyword("following", position, chars).is_ok() {
+        RollPolicy::Following
+    } else if eat_keyword("preceding", position, chars).is_ok() {
+        RollPolicy::Preceding
+    } else {
+        eat_keyword("modified following", position, chars)?;
+        RollPolicy::ModifiedFollowing
+    };
+
+    eat_whitespace(position, chars)?;
+    eat_keyword("business day", position, chars)?;
+
+    Ok(policy)
+}
+
+// Looks ahead and checks if the upcoming block is an exception specification
+// like "except on December 25", without actually advancing the pointer.
+fn expect_exceptions(position: &usize, chars: &[char]) -> bool {
+    expect_sequence(" except ", position, chars)
+}
+
+// Matches, parses and returns a collection of exceptions like "except on
+// Sundays, December 25 and public holidays".
+fn match_exceptions(position: &mut usize, chars: &[char]) -> Result<Vec<Exception>, Error> {
+    eat_keyword("except", position, chars)?;
+    eat_whitespace(position, chars)?;
+    eat_keyword("on", position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let mut tokens = match_exception(position, chars)?;
+
+    loop {
+        if expect_sequence(",", position, chars) {
+            *position += 1;
+            eat_whitespace(position, chars)?;
+        } else if expect_sequence(" and", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("and", position, chars)?;
+            eat_whitespace(position, chars)?;
+        } else {
+            break;
+        }
+
+        tokens.append(&mut match_exception(position, chars)?);
+    }
+
+    if *position < chars.len() {
+        let err = SyntaxError {
+            position: *position,
+            expected: "either ',', 'and' or the end of the expression".to_string(),
+            continues: chars
+                .get(*position..*position + 10)
+                .or(chars.get(*position..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    Ok(tokens)
+}
+
+// Matches and parses a single exception, which is either a date (e.g.
+// "2024-12-24"), a day of the year (e.g. "December 25"), a range of either
+// of them, weekdays in plural (e.g. "Sundays", "weekends") or the name of a
+// list of holidays (e.g. "public holidays").
+fn match_exception(position: &mut usize, chars: &[char]) -> Result<Vec<Exception>, Error> {
+    let next = chars
+        .get(*position)
+        .copied()
+        .ok_or(Error::UnexpectedEndOfInput)?;
+
+    if next.is_ascii_digit() {
+        let first = eat_date(position, chars)?;
+
+        if expect_sequence(" to ", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("to", position, chars)?;
+            eat_whitespace(position, chars)?;
+            let last = eat_date(position, chars)?;
+            return Ok(vec![Exception::DateRange(first, last)]);
+        }
+
+        return Ok(vec![Exception::Date(first)]);
+    }
+
+    if eat_keyword("weekdays", position, chars).is_ok() {
+        return Ok(expand_weekdays(Weekday::Monday, Weekday::Friday)
+            .into_iter()
+            .map(|(day, _)| Exception::Weekday(day))
+            .collect());
+    }
+
+    if eat_keyword("weekends", position, chars).is_ok() {
+        return Ok(expand_weekdays(Weekday::Saturday, Weekday::Sunday)
+            .into_iter()
+            .map(|(day, _)| Exception::Weekday(day))
+            .collect());
+    }
+
+    if next.is_alphabetic() && next.is_lowercase() {
+        return Ok(vec![match_holidays(position, chars)?]);
+    }
+
+    let start_pos = *position;
+
+    if eat_month(position, chars).is_ok() {
+        // Rewind in order to parse the day of the year as a whole.
+        *position = start_pos;
+        let first = match_day_of_year(position, chars)?;
+
+        if expect_sequence(" to ", position, chars) {
+            eat_whitespace(position, chars)?;
+            eat_keyword("to", position, chars)?;
+            eat_whitespace(position, chars)?;
+            let last = match_day_of_year(position, chars)?;
+            return Ok(vec![Exception::DayRange(first, last)]);
+        }
+
+        return Ok(vec![Exception::Day(first.0, first.1)]);
+    }
+
+    Ok(vec![Exception::Weekday(eat_weekday(
+        position, chars, false,
+    )?)])
+}
+
+// Matches and parses a day of the year like "December 25".
+fn match_day_of_year(position: &mut usize, chars: &[char]) -> Result<(Month, u8), Error> {
+    let month = eat_month(position, chars)?;
+    eat_whitespace(position, chars)?;
+
+    let start_pos = *position;
+    let day = eat_number(position, chars)?;
+
+    // Use a leap year here in order to accept February 29.
+    if day == 0 || day > month.length(2000) as u32 {
+        let err = SyntaxError {
+            position: start_pos,
+            expected: format!("a day of {} in the range 1..={}", month, month.length(2000)),
+            continues: chars
+                .get(start_pos..start_pos + 10)
+                .or(chars.get(start_pos..))
+                .unwrap()
+                .iter()
+                .collect::<String>(),
+        };
+        return Err(Error::Syntax(err));
+    }
+
+    Ok((month, day as u8))
+}
+
+// Matches the name of a list of holidays, which consists of lowercase words
+// and ends with "holidays", e.g. "holidays" or "public holidays".
+fn match_holidays(position: &mut usize, chars: &[char]) -> Result<Exception, Error> {
+    let start_pos = *position;
+
+    loop {
+        let word_start = *position;
+
+        while chars
+            .get(*position)
+            .map_or(false, |c| c.is_alphabetic() && c.is_lowercase())
+        {
+            *position += 1;
+        }
+
+        let word: String = chars[word_start..*position].iter().collect();
+
+        if word == "holidays" {
+            break;
+        }
+
+        if *position >= chars.len() {
+            return Err(Error::UnexpectedEndOfInput);
+        }
+
+        if word.is_empty() || !expect_sequence(" ", position, chars) {
+            let err = SyntaxError {
+                position: start_pos,
+                expected: "the name of a list of holidays, e.g. 'public holidays'".to_string(),
+                continues: chars
+                    .get(start_pos..start_pos + 10)
+                    .or(chars.get(start_pos..))
+                    .unwrap()
+                    .iter()
+                    .collect::<String>(),
+            };
+            return Err(Error::Syntax(err));
+        }
+
+        eat_whitespace(position, chars)?;
+    }
+
+    let name: String = chars[start_pos..*position].iter().collect();
+
+    Ok(Exception::Holidays(name))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use time::macros::time;
+    use time::macros::{date, offset, time};
 
     #[test]
     fn test_parse_single_block() {
         let spec = ParsedSchedule {
-            times: vec![time!(07:30:00), time!(17:00:00), time!(04:00:00)],
+            times: TimeSpec::List(vec![time!(07:30:00), time!(17:00:00), time!(04:00:00)]),
             days: Some(vec![
                 (Weekday::Monday, None),
                 (Weekday::Wednesday, None),
                 (Weekday::Friday, Some(WeekdayModifier::Last)),
             ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
             weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: None,
+            zone: None,
         };
         assert_eq!(
             parse("at 07:30 AM, 5 PM and 4 AM on Mondays and Wednesdays and the last Friday in odd weeks"),
             Ok(spec)
         );
     }
+
+    #[test]
+    fn test_parse_every_full_hour() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::EveryFullHour,
+            days: Some(vec![(Weekday::Monday, Some(WeekdayModifier::First))]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Even),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at every full hour on the first Monday in even weeks"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_24_hour_times() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(18:30:00), time!(06:00:00), time!(00:15:00)]),
+            days: Some(vec![(Weekday::Friday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(parse("at 18:30, 6 am and 0:15 on Fridays"), Ok(spec));
+    }
+
+    #[test]
+    fn test_parse_monthdays() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: Some(MonthDays {
+                days: vec![MonthDay::Day(1), MonthDay::Day(15), MonthDay::Last],
+                clamp: false,
+            }),
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on the 1st, 15th and the last day of the month"),
+            Ok(spec)
+        );
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: Some(MonthDays {
+                days: vec![MonthDay::Day(31)],
+                clamp: true,
+            }),
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Even),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on the 31st of the month or the last day in even weeks"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_months() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![(Weekday::Monday, Some(WeekdayModifier::First))]),
+            monthdays: None,
+            business_days: None,
+            months: Some(vec![Month::January, Month::April, Month::July]),
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on the first Monday in January, April and July"),
+            Ok(spec)
+        );
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: Some(vec![Month::November, Month::December, Month::January]),
+            weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM from November to January in odd weeks"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_weekday_ranges() {
+        let weekdays = vec![
+            (Weekday::Monday, None),
+            (Weekday::Tuesday, None),
+            (Weekday::Wednesday, None),
+            (Weekday::Thursday, None),
+            (Weekday::Friday, None),
+        ];
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(weekdays.clone()),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(parse("at 6 AM on weekdays"), Ok(spec.clone()));
+        assert_eq!(parse("at 6 AM on Monday through Friday"), Ok(spec.clone()));
+        assert_eq!(parse("at 6 AM (Mon-Fri)"), Ok(spec));
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![
+                (Weekday::Friday, None),
+                (Weekday::Saturday, None),
+                (Weekday::Sunday, None),
+                (Weekday::Monday, None),
+                (Weekday::Wednesday, Some(WeekdayModifier::First)),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on Friday through Monday and the first Wednesday"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_week_without_weekdays() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(parse("at 6 AM in odd weeks"), Ok(spec));
+    }
+
+    #[test]
+    fn test_parse_week_cycle() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![(Weekday::Monday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Cycle {
+                interval: 3,
+                anchor: date!(2024 - 01 - 01),
+            }),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on Mondays every 3rd week starting 2024-01-01"),
+            Ok(spec)
+        );
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Cycle {
+                interval: 2,
+                anchor: date!(2024 - 01 - 03),
+            }),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM every other week starting 2024-01-03"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_week_numbers() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Numbers(vec![52])),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(parse("at 6 AM in week 52"), Ok(spec));
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![(Weekday::Monday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Numbers(vec![1, 2, 10, 11, 12, 52, 53])),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on Mondays in weeks 10 to 12, 52 to 2 and 11"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![
+                (Weekday::Friday, Some(WeekdayModifier::Fifth)),
+                (Weekday::Thursday, Some(WeekdayModifier::SecondToLast)),
+                (Weekday::Monday, Some(WeekdayModifier::ThirdToLast)),
+                (Weekday::Tuesday, Some(WeekdayModifier::Second)),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on the fifth Friday, the second-to-last Thursday, the 3rd to last Monday and the 2nd Tuesday"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::Interval {
+                step: Duration::minutes(15),
+                start: time!(09:00:00),
+                end: time!(17:00:00),
+            },
+            days: Some(vec![(Weekday::Monday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("every 15 minutes between 9 AM and 5 PM on Mondays"),
+            Ok(spec.clone())
+        );
+        assert_eq!(
+            parse("at every 15 minutes between 9 AM and 5 PM on Mondays"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_exceptions() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![
+                (Weekday::Monday, None),
+                (Weekday::Tuesday, None),
+                (Weekday::Wednesday, None),
+                (Weekday::Thursday, None),
+                (Weekday::Friday, None),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: Some(vec![
+                Exception::Holidays("public holidays".to_string()),
+                Exception::Day(Month::December, 24),
+                Exception::DayRange((Month::December, 27), (Month::January, 2)),
+                Exception::Date(date!(2024 - 05 - 10)),
+                Exception::DateRange(date!(2024 - 07 - 01), date!(2024 - 07 - 14)),
+                Exception::Weekday(Weekday::Friday),
+            ]),
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 AM on weekdays in odd weeks except on public holidays, December 24, December 27 to January 2, 2024-05-10, 2024-07-01 to 2024-07-14 and Fridays"),
+            Ok(spec)
+        );
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: Some(vec![
+                Exception::Weekday(Weekday::Saturday),
+                Exception::Weekday(Weekday::Sunday),
+            ]),
+            zone: None,
+        };
+        assert_eq!(parse("at 6 AM except on weekends"), Ok(spec));
+    }
+
+    #[test]
+    fn test_parse_business_days() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(08:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: Some(BusinessDays::Every),
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(parse("at 8 AM on business days"), Ok(spec));
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(18:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: Some(BusinessDays::Last),
+            months: Some(vec![Month::March, Month::June]),
+            weeks: None,
+            roll: None,
+            exceptions: Some(vec![Exception::Holidays("bank holidays".to_string())]),
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 6 PM on the last business day of the month in March and June except on bank holidays"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_roll() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(09:00:00)]),
+            days: None,
+            monthdays: Some(MonthDays {
+                days: vec![MonthDay::Day(1)],
+                clamp: false,
+            }),
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: Some(RollPolicy::Following),
+            exceptions: None,
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 9 AM on the 1st of the month or the following business day"),
+            Ok(spec)
+        );
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(09:00:00)]),
+            days: None,
+            monthdays: Some(MonthDays {
+                days: vec![MonthDay::Day(31)],
+                clamp: true,
+            }),
+            business_days: None,
+            months: Some(vec![Month::December]),
+            weeks: None,
+            roll: Some(RollPolicy::ModifiedFollowing),
+            exceptions: Some(vec![Exception::Date(date!(2024 - 12 - 31))]),
+            zone: None,
+        };
+        assert_eq!(
+            parse("at 9 AM on the 31st of the month or the last day in December or the modified following business day except on 2024-12-31"),
+            Ok(spec)
+        );
+    }
+
+    #[test]
+    fn test_parse_zone() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(09:00:00)]),
+            days: Some(vec![(Weekday::Monday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: Some(Zone::Offset(UtcOffset::UTC)),
+        };
+        assert_eq!(parse("at 9 AM on Mondays UTC"), Ok(spec));
+
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(09:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: None,
+            zone: Some(Zone::Offset(offset!(-03:30))),
+        };
+        assert_eq!(parse("at 9 AM in odd weeks -03:30"), Ok(spec));
+
+        assert!(matches!(parse("at 9 AM +2"), Err(Error::Syntax(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_parse_time_zone() {
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(09:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: Some(Zone::TimeZone(TimeZone::named("Europe/London").unwrap())),
+        };
+        assert_eq!(parse("at 9 AM Europe/London"), Ok(spec));
+
+        assert_eq!(
+            parse("at 9 AM Europe/Atlantis"),
+            Err(Error::UnknownTimeZone("Europe/Atlantis".to_string()))
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tz"))]
+    fn test_parse_time_zone_without_feature() {
+        assert_eq!(
+            parse("at 9 AM Europe/London"),
+            Err(Error::UnknownTimeZone("Europe/London".to_string()))
+        );
+    }
 }
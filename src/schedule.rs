@@ -1,9 +1,30 @@
+use crate::calendar::{Calendar, HolidayList, RollPolicy};
 use crate::error::*;
 use crate::parse::parse;
+#[cfg(feature = "tz")]
+use crate::timezone::TimeZone;
 use crate::types::*;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::iter::Iterator;
 use std::str::FromStr;
-use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use std::sync::Arc;
+use time::util::weeks_in_year;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
+
+// The number of days in a full cycle of the Gregorian calendar.
+const DAYS_IN_400_YEARS: i64 = 146_097;
+
+// The maximum number of days that a date is moved in search of a business day.
+const MAX_ROLL_DAYS: u8 = 31;
+
+// Named calendars that are attached to an iterator.
+type Calendars = HashMap<String, Arc<dyn Calendar + Send + Sync>>;
+
+// The upcoming date of each ParsedSchedule of a MultiSchedule along with its
+// index, ordered so that the earliest date is on top.
+type DateQueue = BinaryHeap<Reverse<(OffsetDateTime, usize)>>;
 
 /// A schedule that is built from an expression and can be iterated
 /// in order to compute the next date(s) that match the specification.
@@ -11,16 +32,154 @@ use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
 pub struct Schedule(ParsedSchedule);
 
 impl Schedule {
-    #[allow(dead_code)]
+    /// Iterate the dates of the schedule starting from the current
+    /// system time. Dates are computed in the zone that is part of the
+    /// expression or else in the current local offset, which may not be
+    /// available in multithreaded programs, see `iter_utc`.
     pub fn iter(&self) -> Result<ScheduleIter, Error> {
         let Schedule(schedule) = self;
-        let iter = ScheduleIter {
-            schedule: schedule.clone(),
-            current: OffsetDateTime::now_local().map_err(Error::IndeterminateOffset)?,
-            skip_outdated: true,
-            offset: None,
+
+        // A zone that is part of the expression takes the place of the
+        // local offset.
+        let now = match schedule.zone {
+            Some(_) => OffsetDateTime::now_utc(),
+            None => OffsetDateTime::now_local().map_err(Error::IndeterminateOffset)?,
+        };
+
+        Ok(self.iter_from(now).skip_outdated(true))
+    }
+
+    /// Iterate the dates of the schedule starting from the current
+    /// system time without querying the local offset. Dates are computed
+    /// in the zone that is part of the expression or else in UTC.
+    pub fn iter_utc(&self) -> ScheduleIter {
+        self.iter_from(OffsetDateTime::now_utc())
+            .skip_outdated(true)
+    }
+
+    /// Iterate the dates of the schedule that precede some instant in
+    /// descending order, e.g. in order to find the most recent date
+    /// before now. Dates are computed in the zone that is part of the
+    /// expression or else in the offset of the given instant.
+    ///
+    /// ```rust
+    /// use cron_lingo::Schedule;
+    /// use std::str::FromStr;
+    /// use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::from_str("at 6 AM on the last Monday").unwrap();
+    /// let mut iter = schedule.rev_iter_from(datetime!(2024-01-01 12:00 +1));
+    /// assert_eq!(iter.next(), Some(Ok(datetime!(2023-12-25 06:00 +1))));
+    /// assert_eq!(iter.next(), Some(Ok(datetime!(2023-11-27 06:00 +1))));
+    /// ```
+    pub fn rev_iter_from(&self, start: OffsetDateTime) -> ScheduleIter {
+        let mut iter = self.iter_from(start);
+        iter.reverse = true;
+        iter
+    }
+
+    /// Check if an instant is a date of the schedule, e.g. in order to
+    /// decide whether to run a job on a tick. The instant is compared in
+    /// the zone that is part of the expression or else in its own offset.
+    ///
+    /// No calendars are attached here, so an expression that refers to a
+    /// list of holidays by name, e.g. "except on public holidays", never
+    /// matches and there is no error to tell why. Use `ScheduleIter::matches`
+    /// on an iterator with the calendar attached instead, which returns
+    /// `Error::UnknownCalendar` if the calendar is missing.
+    ///
+    /// ```rust
+    /// use cron_lingo::Schedule;
+    /// use std::str::FromStr;
+    /// use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::from_str("at 6 AM on the first Monday").unwrap();
+    /// assert!(schedule.matches(datetime!(2024-03-04 06:00 +1)));
+    /// assert!(!schedule.matches(datetime!(2024-03-11 06:00 +1)));
+    /// ```
+    pub fn matches(&self, instant: OffsetDateTime) -> bool {
+        let Schedule(schedule) = self;
+
+        matches_instant(
+            instant,
+            schedule,
+            &HashMap::new(),
+            None,
+            schedule.zone.as_ref(),
+            GapPolicy::ShiftForward,
+            OverlapPolicy::Earliest,
+        )
+        .unwrap_or(false)
+    }
+
+    /// Iterate the dates of the schedule within a half-open range, i.e.
+    /// including the start and excluding the end. Dates are computed in the
+    /// zone that is part of the expression or else in the offset of the
+    /// start.
+    ///
+    /// ```rust
+    /// use cron_lingo::Schedule;
+    /// use std::str::FromStr;
+    /// use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::from_str("at 6 AM on Mondays").unwrap();
+    /// let start = datetime!(2024-01-01 06:00 UTC);
+    /// let end = datetime!(2024-01-15 06:00 UTC);
+    /// assert_eq!(schedule.occurrences_between(start, end).count(), 2);
+    /// ```
+    pub fn occurrences_between(&self, start: OffsetDateTime, end: OffsetDateTime) -> ScheduleIter {
+        // The start is included, unless it is the first representable
+        // point in time.
+        let mut iter = self.iter_from(start.checked_sub(Duration::NANOSECOND).unwrap_or(start));
+        iter.end = Some(end);
+        iter
+    }
+
+    /// Count the dates of the schedule within a half-open range without
+    /// computing each of them, see `occurrences_between`. Expressions that
+    /// refer to calendars require `ScheduleIter::count_dates` instead.
+    pub fn count_between(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<usize, Error> {
+        self.occurrences_between(start, end).count_dates()
+    }
+
+    /// Iterate the dates of the schedule that follow some instant, e.g.
+    /// in order to backfill dates in the past. Dates are computed in the
+    /// zone that is part of the expression or else in the offset of the
+    /// given instant. Unlike `iter`, dates in the past are not skipped.
+    ///
+    /// ```rust
+    /// use cron_lingo::Schedule;
+    /// use std::str::FromStr;
+    /// use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::from_str("at 6 AM on Mondays").unwrap();
+    /// let mut iter = schedule.iter_from(datetime!(2024-01-01 12:00 +1));
+    /// assert_eq!(iter.next(), Some(Ok(datetime!(2024-01-08 06:00 +1))));
+    /// ```
+    pub fn iter_from(&self, start: OffsetDateTime) -> ScheduleIter {
+        let Schedule(schedule) = self;
+
+        let current = match schedule.zone {
+            Some(ref zone) => to_offset(start, zone.offset_at(start)),
+            None => start,
         };
-        Ok(iter)
+
+        ScheduleIter {
+            schedule: schedule.clone(),
+            current,
+            skip_outdated: false,
+            zone: schedule.zone.clone(),
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
     }
 }
 
@@ -47,7 +206,7 @@ impl std::ops::Add<Schedule> for Schedule {
     fn add(self, other: Self) -> Self::Output {
         let Schedule(first) = self;
         let Schedule(second) = other;
-        MultiSchedule(vec![first, second])
+        MultiSchedule(vec![first, second], vec![(), ()])
     }
 }
 
@@ -57,74 +216,212 @@ pub struct ScheduleIter {
     schedule: ParsedSchedule,
     current: OffsetDateTime,
     skip_outdated: bool,
-    offset: Option<UtcOffset>,
+    zone: Option<Zone>,
+    calendars: Calendars,
+    roll: Option<RollPolicy>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+    reverse: bool,
+    end: Option<OffsetDateTime>,
 }
 
 impl ScheduleIter {
-    /// By default the `next` method will not return a date that is
-    /// in the past but compute the next future data. This method
-    /// allows to change the iterators default behaviour.
+    /// By default the `next` method of iterators created with `iter` or
+    /// `iter_utc` will not return a date that is in the past but compute
+    /// the next future date, while iterators created with `iter_from`
+    /// return every date that follows the start. This method allows to
+    /// change the iterators default behaviour.
     pub fn skip_outdated(mut self, skip: bool) -> ScheduleIter {
         self.skip_outdated = skip;
         self
     }
 
-    /// By default the iterator returns dates in the current local
-    /// offset taken from the system. This method allows to change
-    /// the iteration behaviour to compute dates in another offset.
+    /// By default the iterator returns dates in the zone that is part
+    /// of the expression or else in the current local offset taken from
+    /// the system. This method allows to change the iteration behaviour
+    /// to compute dates in another offset, which takes precedence over a
+    /// zone that is part of the expression.
     pub fn assume_offset(mut self, offset: UtcOffset) -> ScheduleIter {
-        self.offset = Some(offset);
+        self.zone = Some(Zone::Offset(offset));
         self
     }
 
-    /// Compute dates in the current local offset. This is also the
-    /// default behaviour and can be used to revert changes to this
-    /// behaviour that were made using `assume_offset`.
+    /// Revert changes that were made using `assume_offset` or `time_zone`,
+    /// so that dates are computed in the zone that is part of the expression
+    /// again or else in the offset of the start, which is the current local
+    /// offset for iterators created with `iter`. A zone that is part of the
+    /// expression is kept, as only an offset or zone set on the iterator
+    /// takes precedence over it.
     pub fn use_local_offset(mut self) -> ScheduleIter {
-        self.offset = None;
+        self.zone = None;
         self
     }
-}
 
-impl Iterator for ScheduleIter {
-    type Item = Result<OffsetDateTime, Error>;
+    /// Compute dates in the wall-clock time of a time zone, so that
+    /// e.g. "at 6 AM" refers to 6 AM local time both in winter and in
+    /// summer. The time zone takes precedence over a zone that is part of
+    /// the expression. Requires the `tz` feature.
+    #[cfg(feature = "tz")]
+    pub fn time_zone(mut self, zone: TimeZone) -> ScheduleIter {
+        self.zone = Some(Zone::TimeZone(zone));
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(offset) = self.offset {
-            self.current = self.current.to_offset(offset);
-        }
+    /// By default wall-clock times that are skipped when the clocks are
+    /// turned forward are shifted forward by the length of the gap. This
+    /// method allows to skip such dates instead. Only applies to time
+    /// zones with daylight saving time, see `time_zone`.
+    pub fn on_gap(mut self, policy: GapPolicy) -> ScheduleIter {
+        self.gap = policy;
+        self
+    }
 
-        if self.skip_outdated {
-            let mut now = match OffsetDateTime::now_local().map_err(Error::IndeterminateOffset) {
-                Ok(n) => n,
-                Err(e) => return Some(Err(e)),
-            };
+    /// By default wall-clock times that occur twice when the clocks are
+    /// turned back are used once at their first occurrence. This method
+    /// allows to use the second occurrence or both instead. Only applies
+    /// to time zones with daylight saving time, see `time_zone`.
+    pub fn on_overlap(mut self, policy: OverlapPolicy) -> ScheduleIter {
+        self.overlap = policy;
+        self
+    }
 
-            if let Some(offset) = self.offset {
-                now = now.to_offset(offset);
-            }
+    /// By default dates that do not fall on a business day are kept as
+    /// they are. This method allows to move such dates to a business day
+    /// nearby instead, while keeping the time of day. A roll specification
+    /// in an expression (e.g. "or the following business day") takes
+    /// precedence over this policy.
+    pub fn roll(mut self, policy: RollPolicy) -> ScheduleIter {
+        self.roll = Some(policy);
+        self
+    }
+
+    /// Attach a named calendar to the iterator. Expressions refer to
+    /// calendars by name in order to exclude their holidays, e.g. "at
+    /// 6 AM on weekdays except on public holidays" requires a calendar
+    /// named "public holidays". Business days (e.g. "on business days")
+    /// are weekdays that are not a holiday in any of the attached
+    /// calendars. Attaching a calendar under a name that already exists
+    /// replaces the previous calendar.
+    pub fn calendar<C>(mut self, name: &str, calendar: C) -> ScheduleIter
+    where
+        C: Calendar + Send + Sync + 'static,
+    {
+        self.calendars.insert(name.to_string(), Arc::new(calendar));
+        self
+    }
+
+    /// Attach a named list of holidays to the iterator. This is a
+    /// shorthand for `calendar` with a `HolidayList`.
+    pub fn holidays<I>(self, name: &str, dates: I) -> ScheduleIter
+    where
+        I: IntoIterator<Item = Date>,
+    {
+        self.calendar(name, dates.into_iter().collect::<HolidayList>())
+    }
+
+    /// Return the latest date before the current position of the
+    /// iterator and move the iterator to it, e.g. the most recent date
+    /// before now for an iterator created with `iter`. This is the
+    /// opposite direction of `next`, so iterators created with
+    /// `rev_iter_from` move forward instead. Outdated dates are never
+    /// skipped in this direction.
+    pub fn prev(&mut self) -> Option<Result<OffsetDateTime, Error>> {
+        self.step(!self.reverse)
+    }
+
+    /// Check if an instant is a date of the schedule, taking the zone,
+    /// calendars and policies of the iterator into account. Returns an
+    /// error if the expression refers to a calendar that is unknown.
+    pub fn matches(&self, instant: OffsetDateTime) -> Result<bool, Error> {
+        matches_instant(
+            instant,
+            &self.schedule,
+            &self.calendars,
+            self.roll,
+            self.zone.as_ref(),
+            self.gap,
+            self.overlap,
+        )
+    }
+
+    /// Count the remaining dates of the iterator without computing each
+    /// of them, e.g. after attaching calendars to an iterator created with
+    /// `occurrences_between`. Like `Iterator::count`, this never returns
+    /// for an iterator that is not bounded.
+    pub fn count_dates(mut self) -> Result<usize, Error> {
+        self.prepare(false);
+        count_dates(
+            self.current,
+            self.end,
+            &self.schedule,
+            &self.calendars,
+            self.roll,
+            self.zone.as_ref().or(self.schedule.zone.as_ref()),
+            self.gap,
+            self.overlap,
+        )
+    }
+
+    // Moves the current date into the zone of the iterator and skips
+    // outdated dates if desired.
+    fn prepare(&mut self, backwards: bool) {
+        if let Some(ref zone) = self.zone {
+            self.current = to_offset(self.current, zone.offset_at(self.current));
+        }
+
+        if self.skip_outdated && !backwards {
+            // Only the instant matters here, so the local offset is not
+            // required and the offset of the iteration is kept.
+            let now = OffsetDateTime::now_utc().to_offset(self.current.offset());
 
             if now > self.current {
                 self.current = now;
             }
         }
+    }
 
-        // Create every possible combination of dates for each
-        // ParsedSchedule and add them to a vector.
-        let candidates: Vec<OffsetDateTime> = compute_dates(self.current, &self.schedule);
+    // Computes the following or preceding date of the schedule and moves
+    // the iterator to it.
+    fn step(&mut self, backwards: bool) -> Option<Result<OffsetDateTime, Error>> {
+        self.prepare(backwards);
 
-        // Iterate the vector of dates and find the next date
-        // by subtracting the current date from each element
-        // in the vector. Return the date that results in the
-        // lowest delta.
-        let next_date = candidates
-            .iter()
-            .min_by_key(|d| **d - self.current)
-            .unwrap();
+        let find_date = if backwards {
+            prev_zoned_date
+        } else {
+            next_zoned_date
+        };
+
+        let next_date = match find_date(
+            self.current,
+            &self.schedule,
+            &self.calendars,
+            self.roll,
+            self.zone.as_ref(),
+            self.gap,
+            self.overlap,
+        ) {
+            Ok(Some(date)) => date,
+            // The schedule never matches again, so the iteration ends.
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
 
-        self.current = *next_date;
+        // A bounded iteration ends at the end of its range.
+        if !backwards && self.end.map_or(false, |end| next_date >= end) {
+            return None;
+        }
+
+        self.current = next_date;
+
+        Some(Ok(next_date))
+    }
+}
+
+impl Iterator for ScheduleIter {
+    type Item = Result<OffsetDateTime, Error>;
 
-        Some(Ok(*next_date))
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step(self.reverse)
     }
 }
 
@@ -132,638 +429,4518 @@ impl Iterator for ScheduleIter {
 /// to compute the next date(s) that match the set of specifications. By
 /// default the computation is based on the current system time, meaning
 /// the iterator will never return a date in the past.
+///
+/// Each schedule may carry a label, e.g. the name of a job, that is
+/// returned along with its dates. Schedules are labeled by adding pairs
+/// of a schedule and a label, while combinations of unlabeled schedules
+/// are labeled with `()`.
+///
+/// ```rust
+/// use cron_lingo::schedule::MultiSchedule;
+/// use cron_lingo::Schedule;
+/// use std::str::FromStr;
+///
+/// fn main() -> Result<(), cron_lingo::error::Error> {
+///     let backup = Schedule::from_str("at 2 AM on Sundays")?;
+///     let report = Schedule::from_str("at 6 PM on weekdays")?;
+///     let jobs = MultiSchedule::from((backup, "backup")) + (report, "report");
+///
+///     let start = time::macros::datetime!(2024-01-01 00:00 UTC);
+///     let (_date, job) = jobs.iter_from(start).next().unwrap()?;
+///     assert_eq!(*job, "report");
+///     Ok(())
+/// }
+/// ```
 #[derive(Debug, PartialEq, Clone)]
-pub struct MultiSchedule(Vec<ParsedSchedule>);
+pub struct MultiSchedule<T = ()>(Vec<ParsedSchedule>, Vec<T>);
 
-impl MultiSchedule {
-    #[allow(dead_code)]
-    pub fn iter(&self) -> Result<MultiScheduleIter, Error> {
-        let MultiSchedule(schedules) = self;
-        let iter = MultiScheduleIter {
-            schedules: &schedules,
-            current: OffsetDateTime::now_local().map_err(Error::IndeterminateOffset)?,
-            skip_outdated: true,
-            offset: None,
-        };
-        Ok(iter)
+impl<T> MultiSchedule<T> {
+    /// Iterate the dates of the schedules starting from the current
+    /// system time. Dates are computed in the zone that is part of the
+    /// respective expression or else in the current local offset, which
+    /// may not be available in multithreaded programs, see `iter_utc`.
+    pub fn iter(&self) -> Result<MultiScheduleIter<'_, T>, Error> {
+        Ok(self.iter_from(self.now()?).skip_outdated(true))
+    }
+
+    // Returns the current system time that `iter` and `into_iter` start
+    // from. The local offset is only needed if some expression lacks a zone.
+    fn now(&self) -> Result<OffsetDateTime, Error> {
+        let MultiSchedule(schedules, _) = self;
+
+        if schedules.iter().all(|schedule| schedule.zone.is_some()) {
+            Ok(OffsetDateTime::now_utc())
+        } else {
+            OffsetDateTime::now_local().map_err(Error::IndeterminateOffset)
+        }
+    }
+
+    /// Iterate the dates of the schedules starting from the current
+    /// system time without querying the local offset. Dates are computed
+    /// in the zone that is part of the respective expression or else in
+    /// UTC.
+    pub fn iter_utc(&self) -> MultiScheduleIter<'_, T> {
+        self.iter_from(OffsetDateTime::now_utc())
+            .skip_outdated(true)
+    }
+
+    /// Iterate the dates of the schedules that precede some instant in
+    /// descending order, e.g. in order to find the most recent date
+    /// before now. Dates are computed in the zone that is part of the
+    /// respective expression or else in the offset of the given instant.
+    pub fn rev_iter_from(&self, start: OffsetDateTime) -> MultiScheduleIter<'_, T> {
+        let mut iter = self.iter_from(start);
+        iter.dates.reverse = true;
+        iter
+    }
+
+    /// Check if an instant is a date of any of the schedules and return
+    /// the label of the first schedule that matches, in the order in which
+    /// the schedules were added. See `Schedule::matches` for details.
+    ///
+    /// Like `Schedule::matches`, schedules whose expression refers to a list
+    /// of holidays by name never match here, as no calendars are attached.
+    /// Use `MultiScheduleIter::matches` with the calendar attached instead,
+    /// which returns `Error::UnknownCalendar` if the calendar is missing.
+    pub fn matches(&self, instant: OffsetDateTime) -> Option<&T> {
+        let MultiSchedule(schedules, labels) = self;
+
+        let index = schedules.iter().position(|schedule| {
+            matches_instant(
+                instant,
+                schedule,
+                &HashMap::new(),
+                None,
+                schedule.zone.as_ref(),
+                GapPolicy::ShiftForward,
+                OverlapPolicy::Earliest,
+            )
+            .unwrap_or(false)
+        })?;
+
+        Some(&labels[index])
+    }
+
+    /// Iterate the dates of the schedules within a half-open range, i.e.
+    /// including the start and excluding the end. Dates are computed in the
+    /// zone that is part of the respective expression or else in the offset
+    /// of the start.
+    pub fn occurrences_between(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> MultiScheduleIter<'_, T> {
+        // The start is included, unless it is the first representable
+        // point in time.
+        let mut iter = self.iter_from(start.checked_sub(Duration::NANOSECOND).unwrap_or(start));
+        iter.dates.end = Some(end);
+        iter
+    }
+
+    /// Count the dates of the schedules within a half-open range without
+    /// computing each of them, see `occurrences_between`. Expressions that
+    /// refer to calendars require `MultiScheduleIter::count_dates` instead.
+    pub fn count_between(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<usize, Error> {
+        self.occurrences_between(start, end).count_dates()
+    }
+
+    /// Iterate the dates of the schedules that follow some instant, e.g.
+    /// in order to backfill dates in the past. Dates are computed in the
+    /// zone that is part of the respective expression or else in the
+    /// offset of the given instant. Unlike `iter`, dates in the past are
+    /// not skipped.
+    pub fn iter_from(&self, start: OffsetDateTime) -> MultiScheduleIter<'_, T> {
+        let MultiSchedule(schedules, labels) = self;
+
+        MultiScheduleIter {
+            dates: MultiDates::new(Cow::Borrowed(schedules), start),
+            labels,
+        }
+    }
+
+    /// Like `iter_from` but the iterator takes ownership of the schedules,
+    /// so that it may be stored or sent to another thread independently of
+    /// the `MultiSchedule`.
+    pub fn into_iter_from(self, start: OffsetDateTime) -> MultiScheduleIntoIter<T> {
+        let MultiSchedule(schedules, labels) = self;
+
+        MultiScheduleIntoIter {
+            dates: MultiDates::new(Cow::Owned(schedules), start),
+            labels,
+        }
+    }
+
+    /// Return the number of schedules.
+    pub fn len(&self) -> usize {
+        let MultiSchedule(schedules, _) = self;
+        schedules.len()
+    }
+
+    /// Check if all schedules were removed. An empty `MultiSchedule`
+    /// does not produce any dates.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate the schedules along with their labels in the order in
+    /// which they were added.
+    pub fn iter_schedules(&self) -> impl Iterator<Item = (Schedule, &T)> {
+        let MultiSchedule(schedules, labels) = self;
+
+        schedules.iter().cloned().map(Schedule).zip(labels.iter())
+    }
+
+    /// Remove the schedule at some index, in the order in which the
+    /// schedules were added, and return it along with its label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> (Schedule, T) {
+        let MultiSchedule(schedules, labels) = self;
+        (Schedule(schedules.remove(index)), labels.remove(index))
+    }
+
+    /// Remove all schedules that carry some label and return them.
+    pub fn remove_label(&mut self, label: &T) -> Vec<Schedule>
+    where
+        T: PartialEq,
+    {
+        let mut removed = vec![];
+
+        self.retain_pairs(|schedule, other| {
+            if other == label {
+                removed.push(Schedule(schedule.clone()));
+                false
+            } else {
+                true
+            }
+        });
+
+        removed
+    }
+
+    /// Keep only the schedules whose label satisfies a predicate.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_pairs(|_, label| f(label));
+    }
+
+    /// Split the combination into its schedules along with their labels.
+    pub fn into_schedules(self) -> Vec<(Schedule, T)> {
+        let MultiSchedule(schedules, labels) = self;
+        schedules.into_iter().map(Schedule).zip(labels).collect()
+    }
+
+    // Keeps only the pairs of schedule and label that satisfy a predicate.
+    fn retain_pairs<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&ParsedSchedule, &T) -> bool,
+    {
+        let MultiSchedule(schedules, labels) = self;
+
+        let (kept_schedules, kept_labels) = std::mem::take(schedules)
+            .into_iter()
+            .zip(std::mem::take(labels))
+            .filter(|(schedule, label)| f(schedule, label))
+            .unzip();
+
+        *schedules = kept_schedules;
+        *labels = kept_labels;
+    }
+}
+
+impl<T: Clone> IntoIterator for MultiSchedule<T> {
+    type Item = Result<(OffsetDateTime, T), Error>;
+    type IntoIter = MultiScheduleIntoIter<T>;
+
+    /// Iterate the dates of the schedules starting from the current
+    /// system time like `iter`, but with an iterator that owns the
+    /// schedules. If the local offset is not available, the iterator
+    /// starts from the current time in UTC like `iter_utc` instead.
+    fn into_iter(self) -> Self::IntoIter {
+        let now = self.now().unwrap_or_else(|_| OffsetDateTime::now_utc());
+        self.into_iter_from(now).skip_outdated(true)
     }
 }
 
 impl From<Schedule> for MultiSchedule {
     fn from(schedule: Schedule) -> Self {
         let Schedule(schedule) = schedule;
-        MultiSchedule(vec![schedule])
+        MultiSchedule(vec![schedule], vec![()])
+    }
+}
+
+impl<T> From<(Schedule, T)> for MultiSchedule<T> {
+    fn from((schedule, label): (Schedule, T)) -> Self {
+        let Schedule(schedule) = schedule;
+        MultiSchedule(vec![schedule], vec![label])
     }
 }
 
 impl std::ops::Add<Schedule> for MultiSchedule {
     type Output = Self;
 
-    fn add(self, other: Schedule) -> Self {
-        let MultiSchedule(mut schedules) = self;
-        let Schedule(schedule) = other;
-        schedules.push(schedule);
-        MultiSchedule(schedules)
+    fn add(mut self, other: Schedule) -> Self {
+        self += other;
+        self
     }
 }
 
-impl std::ops::Add<MultiSchedule> for MultiSchedule {
+impl<T> std::ops::Add<(Schedule, T)> for MultiSchedule<T> {
     type Output = Self;
 
-    fn add(self, other: MultiSchedule) -> Self {
-        let MultiSchedule(mut schedules) = self;
-        let MultiSchedule(mut other_schedules) = other;
-        schedules.append(&mut other_schedules);
-        MultiSchedule(schedules)
+    fn add(mut self, other: (Schedule, T)) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<T> std::ops::Add<MultiSchedule<T>> for MultiSchedule<T> {
+    type Output = Self;
+
+    fn add(mut self, other: MultiSchedule<T>) -> Self {
+        self += other;
+        self
     }
 }
 
 impl std::ops::AddAssign<Schedule> for MultiSchedule {
     fn add_assign(&mut self, other: Schedule) {
-        let MultiSchedule(schedules) = self;
-        let Schedule(schedule) = other;
+        *self += (other, ());
+    }
+}
+
+impl<T> std::ops::AddAssign<(Schedule, T)> for MultiSchedule<T> {
+    fn add_assign(&mut self, (schedule, label): (Schedule, T)) {
+        let MultiSchedule(schedules, labels) = self;
+        let Schedule(schedule) = schedule;
         schedules.push(schedule);
+        labels.push(label);
     }
 }
 
-impl std::ops::AddAssign<MultiSchedule> for MultiSchedule {
-    fn add_assign(&mut self, other: MultiSchedule) {
-        let MultiSchedule(schedules) = self;
-        let MultiSchedule(mut other_schedules) = other;
+impl<T> std::ops::AddAssign<MultiSchedule<T>> for MultiSchedule<T> {
+    fn add_assign(&mut self, other: MultiSchedule<T>) {
+        let MultiSchedule(schedules, labels) = self;
+        let MultiSchedule(mut other_schedules, mut other_labels) = other;
         schedules.append(&mut other_schedules);
+        labels.append(&mut other_labels);
+    }
+}
+
+impl<T> std::ops::Sub<Schedule> for MultiSchedule<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Schedule) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<T> std::ops::SubAssign<Schedule> for MultiSchedule<T> {
+    /// Remove all schedules that are equal to another schedule regardless
+    /// of their labels. Schedules are compared by their specification, so
+    /// e.g. "at 6 PM" and "at 18:00" are equal.
+    fn sub_assign(&mut self, other: Schedule) {
+        let Schedule(other) = other;
+        self.retain_pairs(|schedule, _| *schedule != other);
     }
 }
 
 /// A wrapper around `MultiSchedule` that keeps track of state during iteration.
-#[derive(Clone)]
-pub struct MultiScheduleIter<'a> {
-    schedules: &'a [ParsedSchedule],
-    current: OffsetDateTime,
-    skip_outdated: bool,
-    offset: Option<UtcOffset>,
+/// Each date is returned along with the label of the schedule that produced
+/// it. A date that several schedules share is returned once for each of them,
+/// in the order in which the schedules were added. See
+/// `MultiScheduleIntoIter` for an iterator that owns the schedules.
+pub struct MultiScheduleIter<'a, T = ()> {
+    dates: MultiDates<'a>,
+    labels: &'a [T],
 }
 
-impl<'a> MultiScheduleIter<'a> {
-    /// By default the `next` method will not return a date that is
-    /// in the past but compute the next future data. This method
-    /// allows to change the iterators default behaviour.
-    pub fn skip_outdated(mut self, skip: bool) -> MultiScheduleIter<'a> {
-        self.skip_outdated = skip;
+impl<'a, T> Clone for MultiScheduleIter<'a, T> {
+    fn clone(&self) -> Self {
+        MultiScheduleIter {
+            dates: self.dates.clone(),
+            labels: self.labels,
+        }
+    }
+}
+
+impl<'a, T> MultiScheduleIter<'a, T> {
+    /// By default the `next` method of iterators created with `iter` or
+    /// `iter_utc` will not return a date that is in the past but compute
+    /// the next future date, while iterators created with `iter_from`
+    /// return every date that follows the start. This method allows to
+    /// change the iterators default behaviour.
+    pub fn skip_outdated(mut self, skip: bool) -> MultiScheduleIter<'a, T> {
+        self.dates.skip_outdated = skip;
         self
     }
-    /// By default the iterator returns dates in the current local
-    /// offset taken from the system. This method allows to change
-    /// the iteration behaviour to compute dates in another offset.
-    pub fn assume_offset(mut self, offset: UtcOffset) -> MultiScheduleIter<'a> {
-        self.offset = Some(offset);
+    /// By default the iterator returns dates in the zone that is part
+    /// of the expression or else in the current local offset taken from
+    /// the system. This method allows to change the iteration behaviour
+    /// to compute dates in another offset, which takes precedence over a
+    /// zone that is part of the expression.
+    pub fn assume_offset(mut self, offset: UtcOffset) -> MultiScheduleIter<'a, T> {
+        self.dates.zone = Some(Zone::Offset(offset));
+        self.dates.queue = None;
         self
     }
 
-    /// Compute dates in the current local offset. This is also the
-    /// default behaviour and can be used to revert changes to this
-    /// behaviour that were made using `assume_offset`.
-    pub fn use_local_offset(mut self) -> MultiScheduleIter<'a> {
-        self.offset = None;
+    /// Revert changes that were made using `assume_offset` or `time_zone`,
+    /// so that dates are computed in the zone that is part of the expression
+    /// again or else in the offset of the start, which is the current local
+    /// offset for iterators created with `iter`. A zone that is part of the
+    /// expression is kept, as only an offset or zone set on the iterator
+    /// takes precedence over it.
+    pub fn use_local_offset(mut self) -> MultiScheduleIter<'a, T> {
+        self.dates.zone = None;
+        self.dates.queue = None;
         self
     }
-}
 
-impl<'a> Iterator for MultiScheduleIter<'a> {
-    type Item = Result<OffsetDateTime, Error>;
+    /// Compute dates in the wall-clock time of a time zone, so that
+    /// e.g. "at 6 AM" refers to 6 AM local time both in winter and in
+    /// summer. The time zone takes precedence over a zone that is part of
+    /// the expression. Requires the `tz` feature.
+    #[cfg(feature = "tz")]
+    pub fn time_zone(mut self, zone: TimeZone) -> MultiScheduleIter<'a, T> {
+        self.dates.zone = Some(Zone::TimeZone(zone));
+        self.dates.queue = None;
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(offset) = self.offset {
-            self.current = self.current.to_offset(offset);
-        }
+    /// By default wall-clock times that are skipped when the clocks are
+    /// turned forward are shifted forward by the length of the gap. This
+    /// method allows to skip such dates instead. Only applies to time
+    /// zones with daylight saving time, see `time_zone`.
+    pub fn on_gap(mut self, policy: GapPolicy) -> MultiScheduleIter<'a, T> {
+        self.dates.gap = policy;
+        self.dates.queue = None;
+        self
+    }
 
-        if self.skip_outdated {
-            let mut now = match OffsetDateTime::now_local().map_err(Error::IndeterminateOffset) {
-                Ok(n) => n,
-                Err(e) => return Some(Err(e)),
-            };
+    /// By default wall-clock times that occur twice when the clocks are
+    /// turned back are used once at their first occurrence. This method
+    /// allows to use the second occurrence or both instead. Only applies
+    /// to time zones with daylight saving time, see `time_zone`.
+    pub fn on_overlap(mut self, policy: OverlapPolicy) -> MultiScheduleIter<'a, T> {
+        self.dates.overlap = policy;
+        self.dates.queue = None;
+        self
+    }
 
-            if let Some(offset) = self.offset {
-                now = now.to_offset(offset);
-            }
+    /// By default dates that do not fall on a business day are kept as
+    /// they are. This method allows to move such dates to a business day
+    /// nearby instead, while keeping the time of day. A roll specification
+    /// in an expression (e.g. "or the following business day") takes
+    /// precedence over this policy.
+    pub fn roll(mut self, policy: RollPolicy) -> MultiScheduleIter<'a, T> {
+        self.dates.roll = Some(policy);
+        self.dates.queue = None;
+        self
+    }
 
-            if now > self.current {
-                self.current = now;
-            }
-        }
+    /// Attach a named calendar to the iterator. Expressions refer to
+    /// calendars by name in order to exclude their holidays, e.g. "at
+    /// 6 AM on weekdays except on public holidays" requires a calendar
+    /// named "public holidays". Business days (e.g. "on business days")
+    /// are weekdays that are not a holiday in any of the attached
+    /// calendars. Attaching a calendar under a name that already exists
+    /// replaces the previous calendar.
+    pub fn calendar<C>(mut self, name: &str, calendar: C) -> MultiScheduleIter<'a, T>
+    where
+        C: Calendar + Send + Sync + 'static,
+    {
+        self.dates
+            .calendars
+            .insert(name.to_string(), Arc::new(calendar));
+        self.dates.queue = None;
+        self
+    }
 
-        // Create every possible combination of dates for each
-        // ParsedSchedule and add them to a vector.
-        let mut candidates: Vec<OffsetDateTime> = vec![];
+    /// Attach a named list of holidays to the iterator. This is a
+    /// shorthand for `calendar` with a `HolidayList`.
+    pub fn holidays<I>(self, name: &str, dates: I) -> MultiScheduleIter<'a, T>
+    where
+        I: IntoIterator<Item = Date>,
+    {
+        self.calendar(name, dates.into_iter().collect::<HolidayList>())
+    }
 
-        for schedule in self.schedules {
-            candidates.append(&mut compute_dates(self.current, schedule));
-        }
+    /// Return the latest date before the current position of the
+    /// iterator and move the iterator to it, e.g. the most recent date
+    /// before now for an iterator created with `iter`. This is the
+    /// opposite direction of `next`, so iterators created with
+    /// `rev_iter_from` move forward instead. Outdated dates are never
+    /// skipped in this direction. Like `next`, the date is returned along
+    /// with the label of the schedule that produced it.
+    pub fn prev(&mut self) -> Option<Result<(OffsetDateTime, &'a T), Error>> {
+        let labels = self.labels;
+        let prev = self.dates.step(!self.dates.reverse)?;
+        Some(prev.map(|(date, index)| (date, &labels[index])))
+    }
 
-        // Iterate the vector of dates and find the next date
-        // by subtracting the current date from each element
-        // in the vector. Return the date that results in the
-        // lowest delta.
-        let next_date = candidates
-            .iter()
-            .min_by_key(|d| **d - self.current)
-            .unwrap();
+    /// Check if an instant is a date of any of the schedules, taking the
+    /// zone, calendars and policies of the iterator into account, and
+    /// return the label of the first schedule that matches. Returns an
+    /// error if an expression refers to a calendar that is unknown.
+    pub fn matches(&self, instant: OffsetDateTime) -> Result<Option<&'a T>, Error> {
+        let labels = self.labels;
+        Ok(self.dates.matches(instant)?.map(|index| &labels[index]))
+    }
+
+    /// Count the remaining dates of the iterator without computing each
+    /// of them, e.g. after attaching calendars to an iterator created with
+    /// `occurrences_between`. Like `Iterator::count`, this never returns
+    /// for an iterator that is not bounded.
+    pub fn count_dates(self) -> Result<usize, Error> {
+        self.dates.count_dates()
+    }
+}
 
-        self.current = *next_date;
+impl<'a, T> Iterator for MultiScheduleIter<'a, T> {
+    type Item = Result<(OffsetDateTime, &'a T), Error>;
 
-        Some(Ok(*next_date))
+    fn next(&mut self) -> Option<Self::Item> {
+        let labels = self.labels;
+        let next = self.dates.step(self.dates.reverse)?;
+        Some(next.map(|(date, index)| (date, &labels[index])))
     }
 }
 
-// Returns a selection of possible next dates according to the rules in a ParsedSchedule.
-fn compute_dates(base: OffsetDateTime, spec: &ParsedSchedule) -> Vec<OffsetDateTime> {
-    let mut candidates = vec![];
-    let today = base.date();
-    let offset = base.offset();
+/// Like `MultiScheduleIter`, but the iterator owns the schedules, so that
+/// it may be stored or sent to another thread independently of the
+/// `MultiSchedule`. Each date is returned along with a clone of the label of
+/// the schedule that produced it.
+#[derive(Clone)]
+pub struct MultiScheduleIntoIter<T = ()> {
+    dates: MultiDates<'static>,
+    labels: Vec<T>,
+}
+
+impl<T> MultiScheduleIntoIter<T> {
+    /// By default the `next` method of iterators created with `into_iter`
+    /// will not return a date that is in the past but compute the next
+    /// future date, while iterators created with `into_iter_from` return
+    /// every date that follows the start. This method allows to change the
+    /// iterators default behaviour.
+    pub fn skip_outdated(mut self, skip: bool) -> MultiScheduleIntoIter<T> {
+        self.dates.skip_outdated = skip;
+        self
+    }
+    /// By default the iterator returns dates in the zone that is part
+    /// of the expression or else in the current local offset taken from
+    /// the system. This method allows to change the iteration behaviour
+    /// to compute dates in another offset, which takes precedence over a
+    /// zone that is part of the expression.
+    pub fn assume_offset(mut self, offset: UtcOffset) -> MultiScheduleIntoIter<T> {
+        self.dates.zone = Some(Zone::Offset(offset));
+        self.dates.queue = None;
+        self
+    }
+
+    /// Revert changes that were made using `assume_offset` or `time_zone`,
+    /// so that dates are computed in the zone that is part of the expression
+    /// again or else in the offset of the start, which is the current local
+    /// offset for iterators created with `iter`. A zone that is part of the
+    /// expression is kept, as only an offset or zone set on the iterator
+    /// takes precedence over it.
+    pub fn use_local_offset(mut self) -> MultiScheduleIntoIter<T> {
+        self.dates.zone = None;
+        self.dates.queue = None;
+        self
+    }
 
-    // For each specified time ...
-    for time in &spec.times {
-        // ... create an OffsetDateTime object for each upcoming weekday ...
-        for i in 0..=6 {
-            let mut date =
-                PrimitiveDateTime::new(today + Duration::days(i), *time).assume_offset(offset);
+    /// Compute dates in the wall-clock time of a time zone, so that
+    /// e.g. "at 6 AM" refers to 6 AM local time both in winter and in
+    /// summer. The time zone takes precedence over a zone that is part of
+    /// the expression. Requires the `tz` feature.
+    #[cfg(feature = "tz")]
+    pub fn time_zone(mut self, zone: TimeZone) -> MultiScheduleIntoIter<T> {
+        self.dates.zone = Some(Zone::TimeZone(zone));
+        self.dates.queue = None;
+        self
+    }
 
-            if date <= base {
-                date += Duration::weeks(1);
-            }
+    /// By default wall-clock times that are skipped when the clocks are
+    /// turned forward are shifted forward by the length of the gap. This
+    /// method allows to skip such dates instead. Only applies to time
+    /// zones with daylight saving time, see `time_zone`.
+    pub fn on_gap(mut self, policy: GapPolicy) -> MultiScheduleIntoIter<T> {
+        self.dates.gap = policy;
+        self.dates.queue = None;
+        self
+    }
 
-            candidates.push(date);
-        }
+    /// By default wall-clock times that occur twice when the clocks are
+    /// turned back are used once at their first occurrence. This method
+    /// allows to use the second occurrence or both instead. Only applies
+    /// to time zones with daylight saving time, see `time_zone`.
+    pub fn on_overlap(mut self, policy: OverlapPolicy) -> MultiScheduleIntoIter<T> {
+        self.dates.overlap = policy;
+        self.dates.queue = None;
+        self
     }
 
-    // ... remove all objects that match none of the desired weekdays (if any)
-    // and increment the remaining dates according to the optional WeekdayModifier
-    // and WeekVariant.
-    if let Some(ref days) = spec.days {
-        let weeks = spec.weeks;
+    /// By default dates that do not fall on a business day are kept as
+    /// they are. This method allows to move such dates to a business day
+    /// nearby instead, while keeping the time of day. A roll specification
+    /// in an expression (e.g. "or the following business day") takes
+    /// precedence over this policy.
+    pub fn roll(mut self, policy: RollPolicy) -> MultiScheduleIntoIter<T> {
+        self.dates.roll = Some(policy);
+        self.dates.queue = None;
+        self
+    }
 
-        candidates = candidates
-            .into_iter()
-            .filter(|c| days.iter().any(|x| x.0 == c.weekday()))
-            .collect();
+    /// Attach a named calendar to the iterator. Expressions refer to
+    /// calendars by name in order to exclude their holidays, e.g. "at
+    /// 6 AM on weekdays except on public holidays" requires a calendar
+    /// named "public holidays". Business days (e.g. "on business days")
+    /// are weekdays that are not a holiday in any of the attached
+    /// calendars. Attaching a calendar under a name that already exists
+    /// replaces the previous calendar.
+    pub fn calendar<C>(mut self, name: &str, calendar: C) -> MultiScheduleIntoIter<T>
+    where
+        C: Calendar + Send + Sync + 'static,
+    {
+        self.dates
+            .calendars
+            .insert(name.to_string(), Arc::new(calendar));
+        self.dates.queue = None;
+        self
+    }
 
-        for candidate in &mut candidates {
-            let day_modifier = days.iter().find(|x| x.0 == candidate.weekday()).unwrap().1;
+    /// Attach a named list of holidays to the iterator. This is a
+    /// shorthand for `calendar` with a `HolidayList`.
+    pub fn holidays<I>(self, name: &str, dates: I) -> MultiScheduleIntoIter<T>
+    where
+        I: IntoIterator<Item = Date>,
+    {
+        self.calendar(name, dates.into_iter().collect::<HolidayList>())
+    }
 
-            while !check_date_validity(candidate, day_modifier, weeks) {
-                *candidate += Duration::weeks(1);
-            }
-        }
+    /// Return the latest date before the current position of the
+    /// iterator and move the iterator to it, see `MultiScheduleIter::prev`.
+    pub fn prev(&mut self) -> Option<Result<(OffsetDateTime, T), Error>>
+    where
+        T: Clone,
+    {
+        let prev = self.dates.step(!self.dates.reverse)?;
+        Some(prev.map(|(date, index)| (date, self.labels[index].clone())))
     }
 
-    // ... and return the filtered date candidates of this ParsedSchedule.
-    candidates
+    /// Check if an instant is a date of any of the schedules and return
+    /// the label of the first schedule that matches, see
+    /// `MultiScheduleIter::matches`.
+    pub fn matches(&self, instant: OffsetDateTime) -> Result<Option<&T>, Error> {
+        Ok(self
+            .dates
+            .matches(instant)?
+            .map(|index| &self.labels[index]))
+    }
+
+    /// Count the remaining dates of the iterator without computing each
+    /// of them, see `MultiScheduleIter::count_dates`.
+    pub fn count_dates(self) -> Result<usize, Error> {
+        self.dates.count_dates()
+    }
 }
 
-// Takes a date and checks its bounds according to optional WeekdayModifiers
-// and/or WeekVariants. Returns false if the date does not match the specified rules.
-fn check_date_validity(
-    date: &OffsetDateTime,
-    weekday_mod: Option<WeekdayModifier>,
-    week_mod: Option<WeekVariant>,
-) -> bool {
-    let is_correct_day = match weekday_mod {
-        Some(modifier) => {
-            let day = date.day();
+impl<T: Clone> Iterator for MultiScheduleIntoIter<T> {
+    type Item = Result<(OffsetDateTime, T), Error>;
 
-            match modifier {
-                WeekdayModifier::First => day <= 7,
-                WeekdayModifier::Second => day > 7 && day <= 14,
-                WeekdayModifier::Third => day > 14 && day <= 21,
-                WeekdayModifier::Fourth => day > 21 && day <= 28,
-                WeekdayModifier::Last => date.month() != (*date + Duration::weeks(1)).month(),
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.dates.step(self.dates.reverse)?;
+        Some(next.map(|(date, index)| (date, self.labels[index].clone())))
+    }
+}
+
+// The state of an iteration over the dates of a set of ParsedSchedules that
+// both MultiScheduleIter and MultiScheduleIntoIter share. Dates are returned
+// along with the index of the ParsedSchedule that produced them, once for
+// each ParsedSchedule that matches a date. Dates that several of them share
+// are ordered by index, so the position of the iteration is the current date
+// along with the index of the ParsedSchedule that was returned last.
+#[derive(Clone)]
+struct MultiDates<'a> {
+    schedules: Cow<'a, [ParsedSchedule]>,
+    current: OffsetDateTime,
+    index: Option<usize>,
+    skip_outdated: bool,
+    zone: Option<Zone>,
+    calendars: Calendars,
+    roll: Option<RollPolicy>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+    reverse: bool,
+    end: Option<OffsetDateTime>,
+    queue: Option<DateQueue>,
+}
+
+impl<'a> MultiDates<'a> {
+    fn new(schedules: Cow<'a, [ParsedSchedule]>, start: OffsetDateTime) -> Self {
+        MultiDates {
+            schedules,
+            current: start,
+            index: None,
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+            queue: None,
+        }
+    }
+
+    // Returns the index of the first schedule that matches an instant.
+    fn matches(&self, instant: OffsetDateTime) -> Result<Option<usize>, Error> {
+        for index in 0..self.schedules.len() {
+            if self.matches_at(instant, index)? {
+                return Ok(Some(index));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Checks if a single schedule matches an instant.
+    fn matches_at(&self, instant: OffsetDateTime, index: usize) -> Result<bool, Error> {
+        let schedule = &self.schedules[index];
+
+        matches_instant(
+            instant,
+            schedule,
+            &self.calendars,
+            self.roll,
+            self.zone.as_ref().or(schedule.zone.as_ref()),
+            self.gap,
+            self.overlap,
+        )
+    }
+
+    // Counts the remaining dates of the iteration, which are the remaining
+    // dates of each schedule.
+    fn count_dates(mut self) -> Result<usize, Error> {
+        self.prepare(false);
+
+        let mut count = 0;
+
+        for (index, schedule) in self.schedules.iter().enumerate() {
+            if self.is_pending(index) && self.end.map_or(true, |end| self.current < end) {
+                count += self.matches_at(self.current, index)? as usize;
+            }
+
+            count += count_dates(
+                self.current,
+                self.end,
+                schedule,
+                &self.calendars,
+                self.roll,
+                self.zone.as_ref().or(schedule.zone.as_ref()),
+                self.gap,
+                self.overlap,
+            )?;
+        }
+
+        Ok(count)
+    }
+
+    // Checks if a schedule follows the schedule that was returned last at
+    // the current date, so that a date it shares with that schedule has not
+    // been returned yet.
+    fn is_pending(&self, index: usize) -> bool {
+        self.index.map_or(false, |last| index > last)
+    }
+
+    // Moves the current date into the zone of the iterator and skips
+    // outdated dates if desired.
+    fn prepare(&mut self, backwards: bool) {
+        if let Some(ref zone) = self.zone {
+            self.current = to_offset(self.current, zone.offset_at(self.current));
+        }
+
+        if self.skip_outdated && !backwards {
+            // Only the instant matters here, so the local offset is not
+            // required and the offset of the iteration is kept.
+            let now = OffsetDateTime::now_utc().to_offset(self.current.offset());
+
+            if now > self.current {
+                self.current = now;
+                self.index = None;
+            }
+        }
+    }
+
+    // Computes the following or preceding date of the schedules and moves
+    // the iterator to it.
+    fn step(&mut self, backwards: bool) -> Option<Result<(OffsetDateTime, usize), Error>> {
+        self.prepare(backwards);
+
+        if backwards {
+            self.step_back()
+        } else {
+            self.step_forward()
+        }
+    }
+
+    // Returns the earliest date of all schedules that follows the current
+    // position. The upcoming date of each schedule is kept in a queue, so
+    // that only the schedule whose date was returned needs to be advanced.
+    fn step_forward(&mut self) -> Option<Result<(OffsetDateTime, usize), Error>> {
+        let mut queue = match self.queue.take() {
+            Some(queue) => queue,
+            None => {
+                let mut queue = BinaryHeap::with_capacity(self.schedules.len());
+
+                for index in 0..self.schedules.len() {
+                    // Schedules that follow the schedule that was returned
+                    // last may still share the current date.
+                    let date = if self.is_pending(index) {
+                        match self.matches_at(self.current, index) {
+                            Ok(true) => Ok(Some(self.current)),
+                            Ok(false) => self.next_date_of(index),
+                            Err(e) => Err(e),
+                        }
+                    } else {
+                        self.next_date_of(index)
+                    };
+
+                    match date {
+                        Ok(Some(date)) => queue.push(Reverse((date, index))),
+                        Ok(None) => {}
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+
+                queue
+            }
+        };
+
+        // Advance the schedule whose date was returned last, as well as the
+        // schedules which fell behind because outdated dates were skipped.
+        // Schedules that never match again are left out.
+        while let Some(Reverse((date, index))) = queue.peek().copied() {
+            if date > self.current || (date == self.current && self.is_pending(index)) {
+                break;
+            }
+
+            queue.pop();
+
+            match self.next_date_of(index) {
+                Ok(Some(date)) => queue.push(Reverse((date, index))),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        // If the queue is empty, none of the schedules matches again and
+        // the iteration ends.
+        let next = queue.peek().map(|Reverse(next)| *next);
+        self.queue = Some(queue);
+        let (next_date, index) = next?;
+
+        // A bounded iteration ends at the end of its range.
+        if self.end.map_or(false, |end| next_date >= end) {
+            return None;
+        }
+
+        // Keep the offset the iteration started with, as the date may
+        // have been computed in the zone of an expression.
+        self.current = to_offset(next_date, self.current.offset());
+        self.index = Some(index);
+
+        Some(Ok((next_date, index)))
+    }
+
+    // Returns the latest date of all schedules that precedes the current
+    // position.
+    fn step_back(&mut self) -> Option<Result<(OffsetDateTime, usize), Error>> {
+        // The queue only holds dates that follow the current position.
+        self.queue = None;
+
+        let mut candidates: Vec<(OffsetDateTime, usize)> = vec![];
+
+        for (index, schedule) in self.schedules.iter().enumerate() {
+            // Schedules that precede the schedule that was returned last
+            // may still share the current date.
+            if self.index.map_or(false, |last| index < last) {
+                match self.matches_at(self.current, index) {
+                    Ok(true) => {
+                        candidates.push((self.current, index));
+                        continue;
+                    }
+                    Ok(false) => {}
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            match prev_zoned_date(
+                self.current,
+                schedule,
+                &self.calendars,
+                self.roll,
+                self.zone.as_ref().or(schedule.zone.as_ref()),
+                self.gap,
+                self.overlap,
+            ) {
+                Ok(Some(date)) => candidates.push((date, index)),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        // Schedules that share the latest date are returned in reverse
+        // order, starting with the last of them.
+        let (prev_date, index) = candidates.into_iter().max()?;
+
+        self.current = to_offset(prev_date, self.current.offset());
+        self.index = Some(index);
+
+        Some(Ok((prev_date, index)))
+    }
+
+    // Returns the next date of a single schedule after the current date.
+    fn next_date_of(&self, index: usize) -> Result<Option<OffsetDateTime>, Error> {
+        let schedule = &self.schedules[index];
+
+        next_zoned_date(
+            self.current,
+            schedule,
+            &self.calendars,
+            self.roll,
+            // The zone of the iterator takes precedence over the zone
+            // that is part of an expression.
+            self.zone.as_ref().or(schedule.zone.as_ref()),
+            self.gap,
+            self.overlap,
+        )
+    }
+}
+
+/// Determines what happens to wall-clock times that do not exist because
+/// the clocks are turned forward, e.g. 2:30 AM on the day daylight saving
+/// time starts in many time zones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// Leave out such dates.
+    Skip,
+    /// Shift such dates forward by the length of the gap, e.g. 2:30 AM
+    /// becomes 3:30 AM if the clocks are turned from 2 AM to 3 AM.
+    ShiftForward,
+}
+
+/// Determines what happens to wall-clock times that occur twice because
+/// the clocks are turned back, e.g. 2:30 AM on the day daylight saving
+/// time ends in many time zones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Use the first occurrence only.
+    Earliest,
+    /// Use the second occurrence only.
+    Latest,
+    /// Use both occurrences.
+    Twice,
+}
+
+// Returns the next date after the base date that matches the rules in a
+// ParsedSchedule in the wall-clock time of a zone, or None if the schedule
+// never matches again. Without a zone, dates are computed in the offset of
+// the base date.
+fn next_zoned_date(
+    base: OffsetDateTime,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+    zone: Option<&Zone>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+) -> Result<Option<OffsetDateTime>, Error> {
+    let zone = match zone {
+        Some(zone) => zone,
+        None => return next_date(base, spec, calendars, roll),
+    };
+
+    // Around a transition of the offset, wall-clock times are not in the
+    // same order as the instants they refer to. So the search starts a
+    // little earlier and picks the earliest instant within the window.
+    let window = zone.max_transition();
+    let start = base.checked_sub(window).unwrap_or(base);
+
+    // Candidates are computed as wall-clock times, which are then mapped
+    // to the actual instants in the zone. The offset of the start date only
+    // serves as a label here.
+    let mut current = to_offset(start, zone.offset_at(start));
+    let mut earliest: Option<OffsetDateTime> = None;
+
+    while let Some(date) = next_date(current, spec, calendars, roll)? {
+        let instants = match zone.resolve(PrimitiveDateTime::new(date.date(), date.time())) {
+            LocalDateTime::Single(d) => vec![d],
+            LocalDateTime::Ambiguous(first, second) => match overlap {
+                OverlapPolicy::Earliest => vec![first],
+                OverlapPolicy::Latest => vec![second],
+                OverlapPolicy::Twice => vec![first, second],
+            },
+            LocalDateTime::Skipped(shifted) => match gap {
+                GapPolicy::Skip => vec![],
+                GapPolicy::ShiftForward => vec![shifted],
+            },
+        };
+
+        for instant in instants {
+            if instant > base && earliest.map_or(true, |next| instant < next) {
+                earliest = Some(instant);
+            }
+        }
+
+        current = date;
+
+        // Later wall-clock times cannot refer to an earlier instant once
+        // they are beyond the window.
+        if earliest.map_or(false, |next| {
+            PrimitiveDateTime::new(next.date(), next.time())
+                .checked_add(window)
+                .map_or(true, |limit| {
+                    PrimitiveDateTime::new(date.date(), date.time()) >= limit
+                })
+        }) {
+            break;
+        }
+    }
+
+    Ok(earliest)
+}
+
+// Returns the latest date before the base date that matches the rules in a
+// ParsedSchedule in the wall-clock time of a zone, or None if the schedule
+// never matched before. Instead of mirroring every rule, dates are searched
+// forward from increasingly earlier points in time, so that exactly the same
+// rules apply in both directions.
+fn prev_zoned_date(
+    base: OffsetDateTime,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+    zone: Option<&Zone>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+) -> Result<Option<OffsetDateTime>, Error> {
+    let period = search_period(spec);
+
+    let min = base.replace_date(Date::MIN).replace_time(Time::MIDNIGHT);
+    let mut window = Duration::HOUR;
+
+    loop {
+        let start = match base.checked_sub(window) {
+            Some(start) if start > min => start,
+            _ => min,
+        };
+
+        let mut current = start;
+        let mut prev_date = None;
+
+        while let Some(date) = next_zoned_date(current, spec, calendars, roll, zone, gap, overlap)?
+        {
+            if date >= base {
+                break;
             }
+
+            prev_date = Some(date);
+            current = date;
+        }
+
+        if prev_date.is_some() {
+            return Ok(prev_date);
+        }
+
+        // Rules that did not match any date within the search period
+        // never matched at all.
+        if start == min || window > period {
+            return Ok(None);
+        }
+
+        window *= 2;
+    }
+}
+
+// Checks if an instant is a date of a ParsedSchedule in the wall-clock time of
+// a zone. Without a zone, the instant is compared in its own offset.
+fn matches_instant(
+    instant: OffsetDateTime,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+    zone: Option<&Zone>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+) -> Result<bool, Error> {
+    let zone = match zone {
+        Some(zone) => zone,
+        None => return matches_local(instant.date(), instant.time(), spec, calendars, roll),
+    };
+
+    let local = to_offset(instant, zone.offset_at(instant));
+    let wall = PrimitiveDateTime::new(local.date(), local.time());
+
+    if matches_local(wall.date(), wall.time(), spec, calendars, roll)? {
+        // Wall-clock times that occur twice only match the occurrences
+        // that the overlap policy allows.
+        let is_used = match zone.resolve(wall) {
+            LocalDateTime::Single(_) => true,
+            LocalDateTime::Ambiguous(first, second) => match overlap {
+                OverlapPolicy::Earliest => instant == first,
+                OverlapPolicy::Latest => instant == second,
+                OverlapPolicy::Twice => true,
+            },
+            LocalDateTime::Skipped(_) => false,
+        };
+
+        if is_used {
+            return Ok(true);
+        }
+    }
+
+    // Dates that are shifted forward out of a gap no longer show their
+    // scheduled wall-clock time, so look at the time before the shift.
+    let before = zone.offset_at(
+        instant
+            .checked_sub(zone.max_transition())
+            .unwrap_or(instant),
+    );
+    let shift = local.offset().whole_seconds() - before.whole_seconds();
+
+    if gap == GapPolicy::ShiftForward && shift > 0 {
+        let wall = wall - Duration::seconds(shift as i64);
+
+        if let LocalDateTime::Skipped(shifted) = zone.resolve(wall) {
+            return Ok(shifted == instant
+                && matches_local(wall.date(), wall.time(), spec, calendars, roll)?);
+        }
+    }
+
+    Ok(false)
+}
+
+// Checks if a wall-clock date and time is a date of a ParsedSchedule,
+// including dates that are moved there by a roll policy.
+fn matches_local(
+    date: Date,
+    time: Time,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+) -> Result<bool, Error> {
+    if !matches_time(&spec.times, time) {
+        return Ok(false);
+    }
+
+    let policy = match spec.roll.or(roll) {
+        Some(policy) => policy,
+        None => return is_scheduled_day(date, spec, calendars),
+    };
+
+    // Look for scheduled days nearby that are moved onto this date.
+    for days in -(MAX_ROLL_DAYS as i64)..=MAX_ROLL_DAYS as i64 {
+        let day = match date.checked_add(Duration::days(days)) {
+            Some(day) => day,
+            None => continue,
+        };
+
+        if is_scheduled_day(day, spec, calendars)?
+            && roll_date(day, policy, calendars) == Some(date)
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// Checks if a time of day is contained in the times of a ParsedSchedule.
+fn matches_time(times: &TimeSpec, time: Time) -> bool {
+    let (step, start, end) = match times {
+        TimeSpec::List(times) => return times.contains(&time),
+        TimeSpec::EveryFullHour => (
+            Duration::HOUR,
+            Time::MIDNIGHT,
+            Time::from_hms(23, 0, 0).unwrap(),
+        ),
+        TimeSpec::Interval { step, start, end } => (*step, *start, *end),
+    };
+
+    start <= time
+        && time <= end
+        && (time - start).whole_nanoseconds() % step.whole_nanoseconds() == 0
+}
+
+// Checks if a day matches the rules in a ParsedSchedule before it is moved
+// by a roll policy, i.e. the same rules that next_day and
+// next_scheduled_date apply.
+fn is_scheduled_day(
+    date: Date,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+) -> Result<bool, Error> {
+    let candidate = PrimitiveDateTime::new(date, Time::MIDNIGHT).assume_utc();
+
+    let is_valid = match spec.monthdays {
+        Some(ref monthdays) => {
+            monthdays
+                .days
+                .iter()
+                .any(|day| is_monthday(date, *day, monthdays.clamp))
+                && check_date_validity(
+                    &candidate,
+                    None,
+                    spec.weeks.as_ref(),
+                    spec.months.as_deref(),
+                )
         }
+        None => match spec.days {
+            // A weekday may be listed several times with different
+            // modifiers, e.g. "on the first and third Monday".
+            Some(ref days) => days.iter().filter(|x| x.0 == date.weekday()).any(|x| {
+                check_date_validity(&candidate, x.1, spec.weeks.as_ref(), spec.months.as_deref())
+            }),
+            None => check_date_validity(
+                &candidate,
+                None,
+                spec.weeks.as_ref(),
+                spec.months.as_deref(),
+            ),
+        },
+    };
+
+    let is_business_day = match spec.business_days {
+        Some(business_days) => check_business_day(date, business_days, calendars),
         None => true,
     };
 
-    let is_correct_week = match week_mod {
-        Some(modifier) => {
-            let week = date.date().iso_week();
+    Ok(is_valid && is_business_day && !is_excluded(date, spec, calendars)?)
+}
+
+// Checks if a date is the given day of the month. Months that are too short
+// to contain the day match on their last day if desired.
+fn is_monthday(date: Date, day: MonthDay, clamp: bool) -> bool {
+    let last_day = date.month().length(date.year());
+
+    match day {
+        MonthDay::Last => date.day() == last_day,
+        MonthDay::Day(d) => date.day() == d || (clamp && d > last_day && date.day() == last_day),
+    }
+}
+
+// Counts the dates of a ParsedSchedule after the base date and before the
+// end date. Every day that contains a date of the ParsedSchedule contains all
+// of its times of day, so only the first date of each day is computed and the
+// remaining times are counted at once. Days around a transition of the offset
+// of a zone are stepped through date by date instead.
+#[allow(clippy::too_many_arguments)]
+fn count_dates(
+    base: OffsetDateTime,
+    end: Option<OffsetDateTime>,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+    zone: Option<&Zone>,
+    gap: GapPolicy,
+    overlap: OverlapPolicy,
+) -> Result<usize, Error> {
+    let mut count = 0;
+    let mut current = base;
+
+    loop {
+        let first = match next_zoned_date(current, spec, calendars, roll, zone, gap, overlap)? {
+            Some(date) if end.map_or(true, |end| date < end) => date,
+            _ => return Ok(count),
+        };
+
+        let is_regular_day = zone.map_or(true, |zone| {
+            let day_start = first.replace_time(Time::MIDNIGHT);
+            let day_start = day_start
+                .checked_sub(zone.max_transition())
+                .unwrap_or(day_start);
+            let day_end = first.replace_time(end_of_day());
+            zone.offset_at(day_start) == first.offset() && zone.offset_at(day_end) == first.offset()
+        });
+
+        if !is_regular_day {
+            count += 1;
+            current = first;
+            continue;
+        }
+
+        count += times_of_day(&spec.times)
+            .into_iter()
+            .filter(|time| *time >= first.time())
+            .filter(|time| end.map_or(true, |end| first.replace_time(*time) < end))
+            .count();
+
+        current = first.replace_time(end_of_day());
+    }
+}
+
+// Returns the next date after the base date that matches the rules in a
+// ParsedSchedule, moved to a business day according to the roll policy of
+// the ParsedSchedule or the iterator, or None if the schedule never matches
+// again.
+fn next_date(
+    base: OffsetDateTime,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+    roll: Option<RollPolicy>,
+) -> Result<Option<OffsetDateTime>, Error> {
+    let policy = match spec.roll.or(roll) {
+        Some(policy) => policy,
+        None => return next_scheduled_date(base, spec, calendars),
+    };
+
+    // Scheduled dates are never moved across a business day, so only
+    // dates after the last business day before the base date may be moved
+    // beyond the base date. Close to the minimum date, the search starts at
+    // the minimum date instead.
+    let start = base.date().previous_day().and_then(|date| {
+        roll_date(date, RollPolicy::Preceding, calendars).or_else(|| {
+            base.date()
+                .checked_sub(Duration::days(MAX_ROLL_DAYS as i64))
+        })
+    });
+
+    let mut current = match start {
+        Some(date) => base.replace_date(date).replace_time(end_of_day()),
+        None => base.replace_date(Date::MIN).replace_time(Time::MIDNIGHT),
+    };
+    let mut next_date: Option<OffsetDateTime> = None;
+
+    while let Some(date) = next_scheduled_date(current, spec, calendars)? {
+        // A date is never moved before the previous business day, so if that
+        // is already later than the best candidate, no later date can be
+        // moved before the candidate either.
+        if let Some(next) = next_date {
+            if roll_date(date.date(), RollPolicy::Preceding, calendars)
+                .map_or(false, |d| d > next.date())
+            {
+                break;
+            }
+        }
+
+        current = date;
+
+        // Dates that cannot be moved to a business day are dropped.
+        let rolled = match roll_date(date.date(), policy, calendars) {
+            Some(day) => date.replace_date(day),
+            None => continue,
+        };
+
+        if rolled > base && next_date.map_or(true, |next| rolled < next) {
+            next_date = Some(rolled);
+        }
+    }
+
+    Ok(next_date)
+}
+
+// Moves a date to a business day according to the roll policy. Returns None
+// if there is no business day within reach.
+fn roll_date(date: Date, policy: RollPolicy, calendars: &Calendars) -> Option<Date> {
+    let following = || {
+        (0..=MAX_ROLL_DAYS as i64)
+            .map_while(|i| date.checked_add(Duration::days(i)))
+            .find(|d| is_business_day(*d, calendars))
+    };
+
+    let preceding = || {
+        (0..=MAX_ROLL_DAYS as i64)
+            .map_while(|i| date.checked_sub(Duration::days(i)))
+            .find(|d| is_business_day(*d, calendars))
+    };
+
+    match policy {
+        RollPolicy::Following => following(),
+        RollPolicy::Preceding => preceding(),
+        RollPolicy::ModifiedFollowing => match following() {
+            Some(d) if d.month() == date.month() => Some(d),
+            _ => preceding(),
+        },
+    }
+}
+
+// Returns the last representable point in time of a day.
+fn end_of_day() -> Time {
+    Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap()
+}
+
+// Converts an instant to another offset. Instants whose date in that offset
+// would be out of range are moved to the first or last representable point in
+// time in that offset instead.
+fn to_offset(instant: OffsetDateTime, offset: UtcOffset) -> OffsetDateTime {
+    instant.checked_to_offset(offset).unwrap_or_else(|| {
+        if instant.year() < 0 {
+            PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT).assume_offset(offset)
+        } else {
+            PrimitiveDateTime::new(Date::MAX, end_of_day()).assume_offset(offset)
+        }
+    })
+}
+
+// Returns the next date after the base date that matches the rules in a
+// ParsedSchedule and is not excluded by it, or None if the schedule never
+// matches again. The next day is computed rule by rule, so only days that
+// are rejected by business day or exception rules are skipped one by one.
+fn next_scheduled_date(
+    base: OffsetDateTime,
+    spec: &ParsedSchedule,
+    calendars: &Calendars,
+) -> Result<Option<OffsetDateTime>, Error> {
+    let limit = search_limit(base.date(), spec);
+
+    // Today is only a candidate if one of the times is still ahead.
+    let mut from = match next_time(&spec.times, Some(base.time())) {
+        Some(_) => base.date(),
+        None => match base.date().next_day() {
+            Some(date) => date,
+            None => return Ok(None),
+        },
+    };
+
+    while let Some(date) = next_day(from, spec, limit) {
+        let is_business_day = match spec.business_days {
+            Some(business_days) => check_business_day(date, business_days, calendars),
+            None => true,
+        };
+
+        if is_business_day && !is_excluded(date, spec, calendars)? {
+            let after = Some(base.time()).filter(|_| date == base.date());
+            let time = next_time(&spec.times, after).unwrap();
+            return Ok(Some(
+                PrimitiveDateTime::new(date, time).assume_offset(base.offset()),
+            ));
+        }
+
+        // Business days and exceptions always refer to whole days, so
+        // continue the search on the next day.
+        from = match date.next_day() {
+            Some(date) => date,
+            None => break,
+        };
+    }
+
+    Ok(None)
+}
+
+// Returns the first day on or after the given date that matches the day,
+// week and month rules of a ParsedSchedule, or None if there is no such day
+// up to the limit or before the end of the calendar.
+fn next_day(from: Date, spec: &ParsedSchedule, limit: Date) -> Option<Date> {
+    if let Some(ref monthdays) = spec.monthdays {
+        return monthdays
+            .days
+            .iter()
+            .filter_map(|day| {
+                next_valid_day(from, spec, limit, |date| {
+                    next_monthday(date, *day, monthdays.clamp)
+                })
+            })
+            .min();
+    }
+
+    match spec.days {
+        Some(ref days) => days
+            .iter()
+            .filter_map(|(weekday, modifier)| {
+                next_valid_day(from, spec, limit, |date| {
+                    next_weekday(date, *weekday, *modifier)
+                })
+            })
+            .min(),
+        // Business days without weekdays fall on Monday to Friday.
+        None if spec.business_days.is_some() => {
+            next_valid_day(from, spec, limit, |date| match date.weekday() {
+                Weekday::Saturday => date.checked_add(Duration::days(2)),
+                Weekday::Sunday => date.next_day(),
+                _ => Some(date),
+            })
+        }
+        None => next_valid_day(from, spec, limit, Some),
+    }
+}
+
+// Returns the first day on or after the given date that matches a day rule,
+// which is passed as a function that returns its first day on or after some
+// date, as well as the week and month rules of a ParsedSchedule. Days that do
+// not match the week or month rules are skipped by jumping straight to the
+// next week or month that may match. All of these functions return None once
+// they reach the end of the calendar.
+fn next_valid_day<F>(from: Date, spec: &ParsedSchedule, limit: Date, first: F) -> Option<Date>
+where
+    F: Fn(Date) -> Option<Date>,
+{
+    let mut date = first(from)?;
+
+    while date <= limit {
+        match (&spec.months, &spec.weeks) {
+            (Some(months), _) if !months.contains(&date.month()) => {
+                date = first(next_month_start(date, months)?)?;
+            }
+            (_, Some(weeks)) if !is_valid_week(date, weeks) => {
+                date = first(next_valid_week(date, weeks)?)?;
+            }
+            _ => return Some(date),
+        }
+    }
+
+    None
+}
+
+// Returns the first day on or after the given date that falls on the weekday
+// and matches the optional WeekdayModifier.
+fn next_weekday(from: Date, weekday: Weekday, modifier: Option<WeekdayModifier>) -> Option<Date> {
+    let next = |date: Date| {
+        let days = weekday.number_days_from_monday() as i64
+            - date.weekday().number_days_from_monday() as i64;
+        date.checked_add(Duration::days(days.rem_euclid(7)))
+    };
+
+    let modifier = match modifier {
+        Some(modifier) => modifier,
+        None => return next(from),
+    };
+
+    let mut month_start = from.replace_day(1).unwrap();
+
+    loop {
+        // Count the occurrences of the weekday in this month in order to
+        // pick the one that the modifier refers to.
+        let first = next(month_start)?;
+        let last_index = (month_start.month().length(month_start.year()) - first.day()) / 7;
+
+        let index = match modifier {
+            WeekdayModifier::First => Some(0),
+            WeekdayModifier::Second => Some(1),
+            WeekdayModifier::Third => Some(2),
+            WeekdayModifier::Fourth => Some(3),
+            WeekdayModifier::Fifth => Some(4),
+            WeekdayModifier::Last => Some(last_index),
+            WeekdayModifier::SecondToLast => last_index.checked_sub(1),
+            WeekdayModifier::ThirdToLast => last_index.checked_sub(2),
+            WeekdayModifier::FourthToLast => last_index.checked_sub(3),
+        };
+
+        if let Some(index) = index.filter(|index| *index <= last_index) {
+            let date = first + Duration::weeks(index as i64);
+
+            if date >= from {
+                return Some(date);
+            }
+        }
+
+        month_start = month_start
+            .replace_day(month_start.month().length(month_start.year()))
+            .ok()?
+            .next_day()?;
+    }
+}
+
+// Returns the first day of the next week after the week of the given date
+// that may match the WeekVariant.
+fn next_valid_week(date: Date, weeks: &WeekVariant) -> Option<Date> {
+    let weekday = Duration::days(date.weekday().number_days_from_monday() as i64);
+
+    match weeks {
+        WeekVariant::Even | WeekVariant::Odd => date.checked_add(Duration::weeks(1) - weekday),
+        WeekVariant::Cycle { interval, anchor } => {
+            let elapsed = weeks_between(*anchor, date).rem_euclid(*interval as i64);
+            date.checked_add(Duration::weeks(*interval as i64 - elapsed) - weekday)
+        }
+        WeekVariant::Numbers(weeks) => next_week_start(date, weeks)?.checked_sub(weekday),
+    }
+}
+
+// Checks if the week of a date matches the WeekVariant.
+fn is_valid_week(date: Date, weeks: &WeekVariant) -> bool {
+    let week = date.iso_week();
+
+    match weeks {
+        WeekVariant::Even => week % 2 == 0,
+        WeekVariant::Odd => week % 2 != 0,
+        WeekVariant::Cycle { interval, anchor } => {
+            weeks_between(*anchor, date).rem_euclid(*interval as i64) == 0
+        }
+        WeekVariant::Numbers(weeks) => weeks.contains(&week),
+    }
+}
+
+// Returns the earliest time of day of a ParsedSchedule after the given time,
+// or the earliest time of day at all. Returns None if all times of the day
+// have passed.
+fn next_time(times: &TimeSpec, after: Option<Time>) -> Option<Time> {
+    let (step, start, end) = match times {
+        TimeSpec::List(times) => {
+            return times
+                .iter()
+                .filter(|time| after.map_or(true, |after| **time > after))
+                .min()
+                .copied()
+        }
+        TimeSpec::EveryFullHour => (
+            Duration::HOUR,
+            Time::MIDNIGHT,
+            Time::from_hms(23, 0, 0).unwrap(),
+        ),
+        TimeSpec::Interval { step, start, end } => (*step, *start, *end),
+    };
+
+    match after {
+        Some(after) if after >= start => {
+            let elapsed = (after - start).whole_seconds();
+            let step = step.whole_seconds();
+            let next = (elapsed / step + 1) * step;
+
+            Some(start + Duration::seconds(next)).filter(|_| next <= (end - start).whole_seconds())
+        }
+        _ => Some(start),
+    }
+}
+
+// Checks if a date is excluded from a ParsedSchedule by one of its exceptions.
+// Returns an error if an exception refers to a list of holidays that is
+// unknown.
+fn is_excluded(date: Date, spec: &ParsedSchedule, calendars: &Calendars) -> Result<bool, Error> {
+    let exceptions = match spec.exceptions {
+        Some(ref exceptions) => exceptions,
+        None => return Ok(false),
+    };
+
+    for exception in exceptions {
+        let is_match = match exception {
+            Exception::Date(d) => date == *d,
+            Exception::DateRange(first, last) => *first <= date && date <= *last,
+            Exception::Day(month, day) => date.month() == *month && date.day() == *day,
+            Exception::DayRange(first, last) => {
+                let day = (date.month() as u8, date.day());
+                let first = (first.0 as u8, first.1);
+                let last = (last.0 as u8, last.1);
+
+                // Ranges like "December 24 to January 6" wrap around the
+                // end of the year.
+                if first <= last {
+                    first <= day && day <= last
+                } else {
+                    first <= day || day <= last
+                }
+            }
+            Exception::Weekday(weekday) => date.weekday() == *weekday,
+            Exception::Holidays(name) => calendars
+                .get(name)
+                .ok_or_else(|| Error::UnknownCalendar(name.clone()))?
+                .is_holiday(date),
+        };
+
+        if is_match {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// Checks if a date matches a business day specification.
+fn check_business_day(date: Date, business_days: BusinessDays, calendars: &Calendars) -> bool {
+    if !is_business_day(date, calendars) {
+        return false;
+    }
+
+    let month_length = date.month().length(date.year());
+
+    // Check that there is no other business day between the date and
+    // the start or end of the month respectively.
+    match business_days {
+        BusinessDays::Every => true,
+        BusinessDays::First => {
+            (1..date.day()).all(|day| !is_business_day(date.replace_day(day).unwrap(), calendars))
+        }
+        BusinessDays::Last => (date.day() + 1..=month_length)
+            .all(|day| !is_business_day(date.replace_day(day).unwrap(), calendars)),
+    }
+}
+
+// Checks if a date is a weekday that is not a holiday in any of the calendars.
+fn is_business_day(date: Date, calendars: &Calendars) -> bool {
+    !matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+        && !calendars.values().any(|c| c.is_holiday(date))
+}
+
+// Returns the date beyond which the search for a matching date is abandoned.
+// The Gregorian calendar repeats itself every 400 years, so rules that do not
+// match any date within this period will never match at all. A cycle of weeks
+// stretches this period by its interval.
+fn search_limit(from: Date, spec: &ParsedSchedule) -> Date {
+    from.checked_add(search_period(spec)).unwrap_or(Date::MAX)
+}
+
+// Returns the period after which the rules of a ParsedSchedule repeat
+// themselves, see search_limit.
+fn search_period(spec: &ParsedSchedule) -> Duration {
+    let factor = match spec.weeks {
+        Some(WeekVariant::Cycle { interval, .. }) => interval as i64,
+        _ => 1,
+    };
+
+    Duration::days(DAYS_IN_400_YEARS * factor)
+}
+
+// Returns the first day of the next month after the month of the given date
+// that is contained in the list of months.
+fn next_month_start(date: Date, months: &[Month]) -> Option<Date> {
+    let (mut year, mut month) = (date.year(), date.month());
+
+    loop {
+        if month == Month::December {
+            year += 1;
+        }
+
+        month = month.next();
+
+        if months.contains(&month) {
+            return Date::from_calendar_date(year, month, 1).ok();
+        }
+    }
+}
+
+// Returns the first date that is equal to or follows the given date and
+// matches the day of the month. Months that are too short to contain the day
+// are either skipped or, if desired, the last day of such a month is used.
+fn next_monthday(from: Date, day: MonthDay, clamp: bool) -> Option<Date> {
+    let (mut year, mut month) = (from.year(), from.month());
+    let mut min_day = from.day();
+
+    loop {
+        let last_day = month.length(year);
+
+        let target = match day {
+            MonthDay::Last => Some(last_day),
+            MonthDay::Day(d) if d <= last_day => Some(d),
+            MonthDay::Day(_) if clamp => Some(last_day),
+            MonthDay::Day(_) => None,
+        };
+
+        if let Some(target) = target {
+            if target >= min_day {
+                return Date::from_calendar_date(year, month, target).ok();
+            }
+        }
+
+        if month == Month::December {
+            year += 1;
+        }
+
+        month = month.next();
+        min_day = 1;
+    }
+}
+
+// Returns every time of day of a ParsedSchedule.
+fn times_of_day(times: &TimeSpec) -> Vec<Time> {
+    let (step, start, end) = match times {
+        TimeSpec::List(times) => return times.clone(),
+        TimeSpec::EveryFullHour => (
+            Duration::HOUR,
+            Time::MIDNIGHT,
+            Time::from_hms(23, 0, 0).unwrap(),
+        ),
+        TimeSpec::Interval { step, start, end } => (*step, *start, *end),
+    };
+
+    let steps = (end - start).whole_seconds() / step.whole_seconds();
+    (0..=steps).map(|i| start + step * i as i32).collect()
+}
+
+// Takes a date and checks its bounds according to optional WeekdayModifiers,
+// WeekVariants and/or months. Returns false if the date does not match the specified rules.
+fn check_date_validity(
+    date: &OffsetDateTime,
+    weekday_mod: Option<WeekdayModifier>,
+    week_mod: Option<&WeekVariant>,
+    months: Option<&[Month]>,
+) -> bool {
+    let is_correct_day = match weekday_mod {
+        Some(modifier) => {
+            let day = date.day();
+
+            match modifier {
+                WeekdayModifier::First => day <= 7,
+                WeekdayModifier::Second => day > 7 && day <= 14,
+                WeekdayModifier::Third => day > 14 && day <= 21,
+                WeekdayModifier::Fourth => day > 21 && day <= 28,
+                WeekdayModifier::Fifth => day > 28,
+                WeekdayModifier::Last => weeks_to_month_end(date) == 0,
+                WeekdayModifier::SecondToLast => weeks_to_month_end(date) == 1,
+                WeekdayModifier::ThirdToLast => weeks_to_month_end(date) == 2,
+                WeekdayModifier::FourthToLast => weeks_to_month_end(date) == 3,
+            }
+        }
+        None => true,
+    };
+
+    let is_correct_week = match week_mod {
+        Some(weeks) => is_valid_week(date.date(), weeks),
+        None => true,
+    };
+
+    let is_correct_month = match months {
+        Some(months) => months.contains(&date.month()),
+        None => true,
+    };
+
+    is_correct_day && is_correct_week && is_correct_month
+}
+
+// Returns the number of times the weekday of the given date occurs after
+// this date within the same month, e.g. 0 for the last Monday of a month.
+fn weeks_to_month_end(date: &OffsetDateTime) -> u8 {
+    (date.month().length(date.year()) - date.day()) / 7
+}
+
+// Returns the same weekday as the given date in the next ISO week after the
+// week of the given date whose number is contained in the list of weeks.
+fn next_week_start(date: Date, weeks: &[u8]) -> Option<Date> {
+    let (mut year, week, weekday) = date.to_iso_week_date();
+
+    // Check the remaining weeks of the current year first ...
+    if let Some(next) = weeks
+        .iter()
+        .filter(|w| **w > week && **w <= weeks_in_year(year))
+        .min()
+    {
+        return Date::from_iso_week_date(year, *next, weekday).ok();
+    }
+
+    // ... then the following years. Week 53 does not exist in every year,
+    // so a list that solely contains week 53 may need to skip a few years.
+    while year < Date::MAX.year() {
+        year += 1;
+
+        if let Some(next) = weeks.iter().filter(|w| **w <= weeks_in_year(year)).min() {
+            return Date::from_iso_week_date(year, *next, weekday).ok();
+        }
+    }
+
+    None
+}
+
+// Returns the number of whole weeks between the weeks (starting on Monday)
+// that the two dates fall into. The result is negative if the second date
+// precedes the first one.
+fn weeks_between(first: Date, second: Date) -> i64 {
+    // Julian days avoid computing a week start before the minimum date.
+    let start_of_week =
+        |date: Date| date.to_julian_day() as i64 - date.weekday().number_days_from_monday() as i64;
+
+    (start_of_week(second) - start_of_week(first)).div_euclid(7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use time::macros::{date, datetime, offset, time};
+    use time::Weekday;
+
+    // Computes the next dates of a ParsedSchedule one after another.
+    fn next_scheduled_dates(
+        base: OffsetDateTime,
+        spec: &ParsedSchedule,
+        count: usize,
+    ) -> Vec<OffsetDateTime> {
+        let mut dates = vec![];
+        let mut current = base;
+
+        for _ in 0..count {
+            current = next_scheduled_date(current, spec, &HashMap::new())
+                .unwrap()
+                .unwrap();
+            dates.push(current);
+        }
+
+        dates
+    }
+
+    #[test]
+    fn test_next_scheduled_date_1() {
+        let base = datetime!(2021-06-04 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(12:00:00), time!(18:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-04 18:00:00 UTC),
+            datetime!(2021-06-05 12:00:00 UTC),
+            datetime!(2021-06-05 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_next_scheduled_date_2() {
+        let base = datetime!(2021-06-04 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(18:00:00)]),
+            days: Some(vec![(Weekday::Monday, None), (Weekday::Thursday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-07 18:00:00 UTC),
+            datetime!(2021-06-10 18:00:00 UTC),
+            datetime!(2021-06-14 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_next_scheduled_date_3() {
+        let base = datetime!(2021-06-04 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(18:00:00)]),
+            days: Some(vec![
+                (Weekday::Monday, Some(WeekdayModifier::Second)),
+                (Weekday::Thursday, None),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-10 18:00:00 UTC),
+            datetime!(2021-06-14 18:00:00 UTC),
+            datetime!(2021-06-17 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_next_scheduled_date_4() {
+        let base = datetime!(2021-06-04 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(12:00:00), time!(18:00:00)]),
+            days: Some(vec![
+                (Weekday::Friday, Some(WeekdayModifier::First)),
+                (Weekday::Thursday, None),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-04 18:00:00 UTC),
+            datetime!(2021-06-10 12:00:00 UTC),
+            datetime!(2021-06-10 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_next_scheduled_date_5() {
+        let base = datetime!(2021-06-12 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00), time!(12:00:00), time!(18:00:00)]),
+            days: Some(vec![
+                (Weekday::Friday, Some(WeekdayModifier::First)),
+                (Weekday::Thursday, None),
+                (Weekday::Monday, Some(WeekdayModifier::Third)),
+            ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-17 06:00:00 UTC),
+            datetime!(2021-06-17 12:00:00 UTC),
+            datetime!(2021-06-17 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_next_scheduled_date_6() {
+        let base = datetime!(2021-06-04 13:38:00 UTC);
+        let spec = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(18:00:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: Some(WeekVariant::Even),
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+        let result = vec![
+            datetime!(2021-06-04 18:00:00 UTC),
+            datetime!(2021-06-05 18:00:00 UTC),
+            datetime!(2021-06-06 18:00:00 UTC),
+        ];
+        assert_eq!(next_scheduled_dates(base, &spec, 3), result);
+    }
+
+    #[test]
+    fn test_schedule_iteration_1() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-09 13:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(01:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-10 01:00:00 UTC)),
+            Ok(datetime!(2021-06-11 01:00:00 UTC)),
+            Ok(datetime!(2021-06-12 01:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_2() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-09 13:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(13:00:00)]),
+                days: Some(vec![(Weekday::Monday, None)]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-14 13:00:00 UTC)),
+            Ok(datetime!(2021-06-21 13:00:00 UTC)),
+            Ok(datetime!(2021-06-28 13:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_3() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-09 13:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00), time!(13:00:00)]),
+                days: Some(vec![
+                    (Weekday::Monday, Some(WeekdayModifier::Third)),
+                    (Weekday::Thursday, None),
+                ]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-10 06:00:00 UTC)),
+            Ok(datetime!(2021-06-10 13:00:00 UTC)),
+            Ok(datetime!(2021-06-17 06:00:00 UTC)),
+            Ok(datetime!(2021-06-17 13:00:00 UTC)),
+            Ok(datetime!(2021-06-21 06:00:00 UTC)),
+            Ok(datetime!(2021-06-21 13:00:00 UTC)),
+            Ok(datetime!(2021-06-24 06:00:00 UTC)),
+            Ok(datetime!(2021-06-24 13:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(8)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_4() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-09 13:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00), time!(13:00:00)]),
+                days: Some(vec![
+                    (Weekday::Monday, Some(WeekdayModifier::Third)),
+                    (Weekday::Thursday, None),
+                ]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: Some(Zone::Offset(offset!(+3))),
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-10 06:00:00 +3)),
+            Ok(datetime!(2021-06-10 13:00:00 +3)),
+            Ok(datetime!(2021-06-17 06:00:00 +3)),
+            Ok(datetime!(2021-06-17 13:00:00 +3)),
+            Ok(datetime!(2021-06-21 06:00:00 +3)),
+            Ok(datetime!(2021-06-21 13:00:00 +3)),
+            Ok(datetime!(2021-06-24 06:00:00 +3)),
+            Ok(datetime!(2021-06-24 13:00:00 +3)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(8)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_5() {
+        let iterator = MultiScheduleIter {
+            dates: MultiDates {
+                current: datetime!(2021-06-09 13:00:00 UTC),
+                index: None,
+                schedules: Cow::Borrowed(&[
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(06:00:00), time!(13:00:00)]),
+                        days: Some(vec![
+                            (Weekday::Monday, Some(WeekdayModifier::Third)),
+                            (Weekday::Thursday, None),
+                        ]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(18:00:00)]),
+                        days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: Some(WeekVariant::Odd),
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                ]),
+                skip_outdated: false,
+                zone: None,
+                calendars: HashMap::new(),
+                roll: None,
+                gap: GapPolicy::ShiftForward,
+                overlap: OverlapPolicy::Earliest,
+                reverse: false,
+                end: None,
+                queue: None,
+            },
+            labels: &[(), ()],
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-10 06:00:00 UTC)),
+            Ok(datetime!(2021-06-10 13:00:00 UTC)),
+            Ok(datetime!(2021-06-17 06:00:00 UTC)),
+            Ok(datetime!(2021-06-17 13:00:00 UTC)),
+            Ok(datetime!(2021-06-21 06:00:00 UTC)),
+            Ok(datetime!(2021-06-21 13:00:00 UTC)),
+            Ok(datetime!(2021-06-24 06:00:00 UTC)),
+            Ok(datetime!(2021-06-24 13:00:00 UTC)),
+            Ok(datetime!(2021-06-26 18:00:00 UTC)),
+            Ok(datetime!(2021-07-01 06:00:00 UTC)),
+            Ok(datetime!(2021-07-01 13:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(11)
+                .map(|item| item.map(|(date, _)| date))
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_6() {
+        let iterator = MultiScheduleIter {
+            dates: MultiDates {
+                current: datetime!(2021-06-18 13:00:00 UTC),
+                index: None,
+                schedules: Cow::Borrowed(&[
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(06:00:00), time!(18:00:00)]),
+                        days: Some(vec![
+                            (Weekday::Monday, Some(WeekdayModifier::Last)),
+                            (Weekday::Thursday, None),
+                        ]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(18:00:00)]),
+                        days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                ]),
+                skip_outdated: false,
+                zone: None,
+                calendars: HashMap::new(),
+                roll: None,
+                gap: GapPolicy::ShiftForward,
+                overlap: OverlapPolicy::Earliest,
+                reverse: false,
+                end: None,
+                queue: None,
+            },
+            labels: &[(), ()],
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-24 06:00:00 UTC)),
+            Ok(datetime!(2021-06-24 18:00:00 UTC)),
+            Ok(datetime!(2021-06-26 18:00:00 UTC)),
+            Ok(datetime!(2021-06-28 06:00:00 UTC)),
+            Ok(datetime!(2021-06-28 18:00:00 UTC)),
+            Ok(datetime!(2021-07-01 06:00:00 UTC)),
+            Ok(datetime!(2021-07-01 18:00:00 UTC)),
+            Ok(datetime!(2021-07-08 06:00:00 UTC)),
+            Ok(datetime!(2021-07-08 18:00:00 UTC)),
+            Ok(datetime!(2021-07-15 06:00:00 UTC)),
+            Ok(datetime!(2021-07-15 18:00:00 UTC)),
+            Ok(datetime!(2021-07-22 06:00:00 UTC)),
+            Ok(datetime!(2021-07-22 18:00:00 UTC)),
+            Ok(datetime!(2021-07-24 18:00:00 UTC)),
+            Ok(datetime!(2021-07-26 06:00:00 UTC)),
+            Ok(datetime!(2021-07-26 18:00:00 UTC)),
+            Ok(datetime!(2021-07-29 06:00:00 UTC)),
+            Ok(datetime!(2021-07-29 18:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(18)
+                .map(|item| item.map(|(date, _)| date))
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_7() {
+        let iterator = MultiScheduleIter {
+            dates: MultiDates {
+                current: datetime!(2021-06-18 13:00:00 UTC),
+                index: None,
+                schedules: Cow::Borrowed(&[
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(06:00:00), time!(18:00:00)]),
+                        days: Some(vec![
+                            (Weekday::Monday, Some(WeekdayModifier::Last)),
+                            (Weekday::Thursday, None),
+                        ]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(18:00:00)]),
+                        days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                ]),
+                skip_outdated: false,
+                zone: Some(Zone::Offset(offset!(+2:30))),
+                calendars: HashMap::new(),
+                roll: None,
+                gap: GapPolicy::ShiftForward,
+                overlap: OverlapPolicy::Earliest,
+                reverse: false,
+                end: None,
+                queue: None,
+            },
+            labels: &[(), ()],
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-24 06:00:00 +2:30)),
+            Ok(datetime!(2021-06-24 18:00:00 +2:30)),
+            Ok(datetime!(2021-06-26 18:00:00 +2:30)),
+            Ok(datetime!(2021-06-28 06:00:00 +2:30)),
+            Ok(datetime!(2021-06-28 18:00:00 +2:30)),
+            Ok(datetime!(2021-07-01 06:00:00 +2:30)),
+            Ok(datetime!(2021-07-01 18:00:00 +2:30)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(7)
+                .map(|item| item.map(|(date, _)| date))
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_8() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-07 21:15:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::EveryFullHour,
+                days: Some(vec![(Weekday::Monday, Some(WeekdayModifier::First))]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-07 22:00:00 UTC)),
+            Ok(datetime!(2021-06-07 23:00:00 UTC)),
+            Ok(datetime!(2021-07-05 00:00:00 UTC)),
+            Ok(datetime!(2021-07-05 01:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_9() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-07 16:20:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::Interval {
+                    step: Duration::minutes(20),
+                    start: time!(09:00:00),
+                    end: time!(17:00:00),
+                },
+                days: Some(vec![(Weekday::Monday, None), (Weekday::Tuesday, None)]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-07 16:40:00 UTC)),
+            Ok(datetime!(2021-06-07 17:00:00 UTC)),
+            Ok(datetime!(2021-06-08 09:00:00 UTC)),
+            Ok(datetime!(2021-06-08 09:20:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_10() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-01-15 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: None,
+                monthdays: Some(MonthDays {
+                    days: vec![MonthDay::Day(15), MonthDay::Day(30)],
+                    clamp: false,
+                }),
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-01-30 06:00:00 UTC)),
+            Ok(datetime!(2021-02-15 06:00:00 UTC)),
+            Ok(datetime!(2021-03-15 06:00:00 UTC)),
+            Ok(datetime!(2021-03-30 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_11() {
+        let iterator = ScheduleIter {
+            current: datetime!(2024-01-31 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: None,
+                monthdays: Some(MonthDays {
+                    days: vec![MonthDay::Day(31)],
+                    clamp: true,
+                }),
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2024-02-29 06:00:00 UTC)),
+            Ok(datetime!(2024-03-31 06:00:00 UTC)),
+            Ok(datetime!(2024-04-30 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_12() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-02-01 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![(Weekday::Monday, Some(WeekdayModifier::First))]),
+                monthdays: None,
+                business_days: None,
+                months: Some(vec![Month::January, Month::July]),
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-07-05 06:00:00 UTC)),
+            Ok(datetime!(2022-01-03 06:00:00 UTC)),
+            Ok(datetime!(2022-07-04 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_13() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-02-27 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: None,
+                months: Some(vec![Month::February]),
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-02-28 06:00:00 UTC)),
+            Ok(datetime!(2022-02-01 06:00:00 UTC)),
+            Ok(datetime!(2022-02-02 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_14() {
+        let iterator = ScheduleIter {
+            current: datetime!(2020-12-20 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![(Weekday::Monday, None), (Weekday::Friday, None)]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: Some(WeekVariant::Cycle {
+                    interval: 3,
+                    anchor: date!(2021 - 01 - 06),
+                }),
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        // The cycle continues across the year with 53 ISO weeks and also
+        // applies to dates that precede the anchor.
+        let result = vec![
+            Ok(datetime!(2021-01-04 06:00:00 UTC)),
+            Ok(datetime!(2021-01-08 06:00:00 UTC)),
+            Ok(datetime!(2021-01-25 06:00:00 UTC)),
+            Ok(datetime!(2021-01-29 06:00:00 UTC)),
+            Ok(datetime!(2021-02-15 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(5)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_15() {
+        let iterator = ScheduleIter {
+            current: datetime!(2020-06-01 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![(Weekday::Wednesday, None)]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: Some(WeekVariant::Numbers(vec![10, 11, 53])),
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2020-12-30 06:00:00 UTC)),
+            Ok(datetime!(2021-03-10 06:00:00 UTC)),
+            Ok(datetime!(2021-03-17 06:00:00 UTC)),
+            Ok(datetime!(2022-03-09 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_16() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-06-01 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![
+                    (Weekday::Friday, Some(WeekdayModifier::Fifth)),
+                    (Weekday::Thursday, Some(WeekdayModifier::SecondToLast)),
+                ]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        };
+
+        let result = vec![
+            Ok(datetime!(2021-06-17 06:00:00 UTC)),
+            Ok(datetime!(2021-07-22 06:00:00 UTC)),
+            Ok(datetime!(2021-07-30 06:00:00 UTC)),
+            Ok(datetime!(2021-08-19 06:00:00 UTC)),
+            Ok(datetime!(2021-09-23 06:00:00 UTC)),
+            Ok(datetime!(2021-10-21 06:00:00 UTC)),
+            Ok(datetime!(2021-10-29 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(7)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_17() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-12-20 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![
+                    (Weekday::Monday, None),
+                    (Weekday::Tuesday, None),
+                    (Weekday::Wednesday, None),
+                    (Weekday::Thursday, None),
+                    (Weekday::Friday, None),
+                ]),
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: Some(vec![
+                    Exception::Day(Month::December, 24),
+                    Exception::DayRange((Month::December, 30), (Month::January, 3)),
+                    Exception::Weekday(Weekday::Wednesday),
+                    Exception::Holidays("public holidays".to_string()),
+                ]),
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .holidays(
+            "public holidays",
+            vec![date!(2021 - 12 - 27), date!(2022 - 01 - 06)],
+        );
+
+        let result = vec![
+            Ok(datetime!(2021-12-21 06:00:00 UTC)),
+            Ok(datetime!(2021-12-23 06:00:00 UTC)),
+            Ok(datetime!(2021-12-28 06:00:00 UTC)),
+            Ok(datetime!(2022-01-04 06:00:00 UTC)),
+            Ok(datetime!(2022-01-07 06:00:00 UTC)),
+            Ok(datetime!(2022-01-10 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(6)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_18() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-12-01 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(18:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: Some(BusinessDays::Last),
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .holidays("public holidays", vec![date!(2021 - 12 - 31)]);
+
+        let result = vec![
+            Ok(datetime!(2021-12-30 18:00:00 UTC)),
+            Ok(datetime!(2022-01-31 18:00:00 UTC)),
+            Ok(datetime!(2022-02-28 18:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_19() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-12-15 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(08:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: Some(BusinessDays::First),
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .calendar(
+            "bank holidays",
+            vec![date!(2022 - 01 - 03)]
+                .into_iter()
+                .collect::<std::collections::HashSet<Date>>(),
+        );
+
+        let result = vec![
+            Ok(datetime!(2022-01-04 08:00:00 UTC)),
+            Ok(datetime!(2022-02-01 08:00:00 UTC)),
+            Ok(datetime!(2022-03-01 08:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_20() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-12-23 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(08:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: Some(BusinessDays::Every),
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .holidays("public holidays", vec![date!(2021 - 12 - 24)])
+        .holidays("company holidays", vec![date!(2021 - 12 - 27)]);
+
+        let result = vec![
+            Ok(datetime!(2021-12-28 08:00:00 UTC)),
+            Ok(datetime!(2021-12-29 08:00:00 UTC)),
+            Ok(datetime!(2021-12-30 08:00:00 UTC)),
+            Ok(datetime!(2021-12-31 08:00:00 UTC)),
+            Ok(datetime!(2022-01-03 08:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(5)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_21() {
+        let iterator = ScheduleIter {
+            current: datetime!(2022-01-03 08:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(09:00:00)]),
+                days: None,
+                monthdays: Some(MonthDays {
+                    days: vec![MonthDay::Day(1)],
+                    clamp: false,
+                }),
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: Some(RollPolicy::Following),
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .holidays("public holidays", vec![date!(2022 - 02 - 01)]);
+
+        let result = vec![
+            Ok(datetime!(2022-01-03 09:00:00 UTC)),
+            Ok(datetime!(2022-02-02 09:00:00 UTC)),
+            Ok(datetime!(2022-03-01 09:00:00 UTC)),
+            Ok(datetime!(2022-04-01 09:00:00 UTC)),
+            Ok(datetime!(2022-05-02 09:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(5)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_22() {
+        let iterator = ScheduleIter {
+            current: datetime!(2021-12-31 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(06:00:00), time!(18:00:00)]),
+                days: None,
+                monthdays: Some(MonthDays {
+                    days: vec![MonthDay::Day(1), MonthDay::Day(2)],
+                    clamp: false,
+                }),
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .roll(RollPolicy::Preceding);
+
+        let result = vec![
+            Ok(datetime!(2021-12-31 18:00:00 UTC)),
+            Ok(datetime!(2022-02-01 06:00:00 UTC)),
+            Ok(datetime!(2022-02-01 18:00:00 UTC)),
+            Ok(datetime!(2022-02-02 06:00:00 UTC)),
+            Ok(datetime!(2022-02-02 18:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(5)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_23() {
+        let iterator = ScheduleIter {
+            current: datetime!(2022-04-01 12:00:00 UTC),
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(vec![time!(18:00:00)]),
+                days: None,
+                monthdays: Some(MonthDays {
+                    days: vec![MonthDay::Last],
+                    clamp: false,
+                }),
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: Some(RollPolicy::ModifiedFollowing),
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .roll(RollPolicy::Following);
+
+        let result = vec![
+            Ok(datetime!(2022-04-29 18:00:00 UTC)),
+            Ok(datetime!(2022-05-31 18:00:00 UTC)),
+            Ok(datetime!(2022-06-30 18:00:00 UTC)),
+            Ok(datetime!(2022-07-29 18:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_schedule_iteration_expression_zone() {
+        let iterator = MultiScheduleIter {
+            dates: MultiDates {
+                current: datetime!(2024-03-20 12:00:00 UTC),
+                index: None,
+                schedules: Cow::Borrowed(&[
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(09:00:00)]),
+                        days: Some(vec![(Weekday::Monday, None)]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: Some(Zone::Offset(offset!(+9))),
+                    },
+                    ParsedSchedule {
+                        times: TimeSpec::List(vec![time!(09:00:00)]),
+                        days: Some(vec![(Weekday::Monday, None)]),
+                        monthdays: None,
+                        business_days: None,
+                        months: None,
+                        weeks: None,
+                        roll: None,
+                        exceptions: None,
+                        zone: None,
+                    },
+                ]),
+                skip_outdated: false,
+                zone: None,
+                calendars: HashMap::new(),
+                roll: None,
+                gap: GapPolicy::ShiftForward,
+                overlap: OverlapPolicy::Earliest,
+                reverse: false,
+                end: None,
+                queue: None,
+            },
+            labels: &[0, 1],
+        };
+
+        let result = vec![
+            Ok((datetime!(2024-03-25 09:00:00 +9), &0)),
+            Ok((datetime!(2024-03-25 09:00:00 UTC), &1)),
+            Ok((datetime!(2024-04-01 09:00:00 +9), &0)),
+        ];
+
+        assert_eq!(
+            iterator
+                .clone()
+                .take(3)
+                .collect::<Vec<Result<(OffsetDateTime, &usize), Error>>>(),
+            result
+        );
+
+        // The zone of the iterator takes precedence, so that both schedules
+        // share their dates.
+        let result = vec![
+            Ok((datetime!(2024-03-25 09:00:00 +1), &0)),
+            Ok((datetime!(2024-03-25 09:00:00 +1), &1)),
+            Ok((datetime!(2024-04-01 09:00:00 +1), &0)),
+        ];
+
+        assert_eq!(
+            iterator
+                .assume_offset(offset!(+1))
+                .take(3)
+                .collect::<Vec<Result<(OffsetDateTime, &usize), Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_into_iter_starts_like_iter() {
+        let expressions = [
+            "at 6 AM and 6 PM",
+            "at 6 AM UTC",
+            "at 9 PM on Fridays +09:00",
+        ];
+
+        for expression in expressions {
+            let multi = MultiSchedule::from(Schedule::from_str(expression).unwrap())
+                + Schedule::from_str("at 1 AM on Mondays UTC").unwrap();
+
+            // The local offset may not be available while the tests run.
+            if let Ok(mut iter) = multi.iter() {
+                assert_eq!(
+                    multi.clone().into_iter().next(),
+                    iter.next().map(|item| item.map(|(date, _)| (date, ()))),
+                    "{}",
+                    expression
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_local_offset_keeps_expression_zone() {
+        let schedule = Schedule::from_str("at 6 AM on Mondays +09:00").unwrap();
+        let start = datetime!(2024-03-20 12:00:00 UTC);
+
+        let mut iter = schedule.iter_from(start).assume_offset(offset!(+1));
+        assert_eq!(iter.next(), Some(Ok(datetime!(2024-03-25 06:00:00 +1))));
+
+        let mut iter = schedule
+            .iter_from(start)
+            .assume_offset(offset!(+1))
+            .use_local_offset();
+        assert_eq!(iter.next(), Some(Ok(datetime!(2024-03-25 06:00:00 +9))));
+
+        let multi = MultiSchedule::from(schedule);
+        let mut iter = multi
+            .iter_from(start)
+            .assume_offset(offset!(+1))
+            .use_local_offset();
+        assert_eq!(
+            iter.next(),
+            Some(Ok((datetime!(2024-03-25 06:00:00 +9), &())))
+        );
+    }
+
+    #[test]
+    fn test_iter_with_expression_zones() {
+        // Every expression has a zone, so the iterators start from the
+        // current UTC time instead of querying the local offset.
+        let first = Schedule::from_str("at 6 AM UTC").unwrap();
+        let second = Schedule::from_str("at 6 PM +09:00").unwrap();
+        assert!(first.iter().is_ok());
+
+        let multi_sched = first + second;
+        let mut iterator = multi_sched.iter().unwrap();
+        assert!(iterator.next().unwrap().unwrap().0 > OffsetDateTime::now_utc());
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_schedule_iteration_time_zone() {
+        let schedule = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(06:00:00)]),
+            days: Some(vec![(Weekday::Monday, None)]),
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+
+        let iterator = ScheduleIter {
+            current: datetime!(2024-03-20 12:00:00 UTC),
+            schedule,
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .time_zone(TimeZone::named("Europe/Berlin").unwrap());
+
+        let result = vec![
+            Ok(datetime!(2024-03-25 06:00:00 +1)),
+            Ok(datetime!(2024-04-01 06:00:00 +2)),
+            Ok(datetime!(2024-04-08 06:00:00 +2)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_schedule_iteration_dst_transitions() {
+        let schedule = ParsedSchedule {
+            times: TimeSpec::List(vec![time!(02:30:00)]),
+            days: None,
+            monthdays: None,
+            business_days: None,
+            months: None,
+            weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
+        };
+
+        let iterator = ScheduleIter {
+            current: datetime!(2024-03-30 00:00:00 UTC),
+            schedule: schedule.clone(),
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .time_zone(TimeZone::named("Europe/Berlin").unwrap());
+
+        let result = vec![
+            Ok(datetime!(2024-03-30 02:30:00 +1)),
+            Ok(datetime!(2024-03-31 03:30:00 +2)),
+            Ok(datetime!(2024-04-01 02:30:00 +2)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+
+        let iterator = ScheduleIter {
+            current: datetime!(2024-10-26 00:00:00 UTC),
+            schedule,
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .time_zone(TimeZone::named("Europe/Berlin").unwrap());
+
+        let result = vec![
+            Ok(datetime!(2024-10-26 02:30:00 +2)),
+            Ok(datetime!(2024-10-27 02:30:00 +2)),
+            Ok(datetime!(2024-10-28 02:30:00 +1)),
+        ];
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+    }
+
+    // Builds an iterator that computes dates at the given times every
+    // day in a time zone.
+    #[cfg(feature = "tz")]
+    fn daily_in_zone(times: Vec<Time>, current: OffsetDateTime, zone: &str) -> ScheduleIter {
+        ScheduleIter {
+            current,
+            schedule: ParsedSchedule {
+                times: TimeSpec::List(times),
+                days: None,
+                monthdays: None,
+                business_days: None,
+                months: None,
+                weeks: None,
+                roll: None,
+                exceptions: None,
+                zone: None,
+            },
+            skip_outdated: false,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
+        }
+        .time_zone(TimeZone::named(zone).unwrap())
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_schedule_iteration_dst_gap_policy() {
+        let iterator = daily_in_zone(
+            vec![time!(02:30:00)],
+            datetime!(2024-03-09 00:00:00 UTC),
+            "America/New_York",
+        )
+        .on_gap(GapPolicy::Skip);
+
+        assert_eq!(
+            iterator
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-03-09 02:30:00 -5)),
+                Ok(datetime!(2024-03-11 02:30:00 -4)),
+            ]
+        );
+
+        let iterator = daily_in_zone(
+            vec![time!(02:30:00)],
+            datetime!(2024-10-05 00:00:00 UTC),
+            "Australia/Sydney",
+        );
+
+        assert_eq!(
+            iterator
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-10-06 03:30:00 +11)),
+                Ok(datetime!(2024-10-07 02:30:00 +11)),
+            ]
+        );
+
+        // The clocks on Lord Howe Island are turned forward by 30 minutes.
+        let iterator = daily_in_zone(
+            vec![time!(02:15:00)],
+            datetime!(2024-10-05 00:00:00 UTC),
+            "Australia/Lord_Howe",
+        );
+
+        assert_eq!(
+            iterator
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-10-06 02:45:00 +11)),
+                Ok(datetime!(2024-10-07 02:15:00 +11)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_schedule_iteration_dst_overlap_policy() {
+        let iterator = daily_in_zone(
+            vec![time!(01:30:00)],
+            datetime!(2024-11-02 12:00:00 UTC),
+            "America/New_York",
+        )
+        .on_overlap(OverlapPolicy::Twice);
+
+        assert_eq!(
+            iterator
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-11-03 01:30:00 -4)),
+                Ok(datetime!(2024-11-03 01:30:00 -5)),
+                Ok(datetime!(2024-11-04 01:30:00 -5)),
+            ]
+        );
+
+        let iterator = daily_in_zone(
+            vec![time!(01:30:00)],
+            datetime!(2024-11-02 12:00:00 UTC),
+            "America/New_York",
+        )
+        .on_overlap(OverlapPolicy::Latest);
+
+        assert_eq!(
+            iterator
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-11-03 01:30:00 -5)),
+                Ok(datetime!(2024-11-04 01:30:00 -5)),
+            ]
+        );
+
+        let iterator = daily_in_zone(
+            vec![time!(02:30:00)],
+            datetime!(2024-04-05 12:00:00 UTC),
+            "Australia/Sydney",
+        )
+        .on_overlap(OverlapPolicy::Twice);
+
+        assert_eq!(
+            iterator
+                .take(4)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-04-06 02:30:00 +11)),
+                Ok(datetime!(2024-04-07 02:30:00 +11)),
+                Ok(datetime!(2024-04-07 02:30:00 +10)),
+                Ok(datetime!(2024-04-08 02:30:00 +10)),
+            ]
+        );
+
+        let iterator = daily_in_zone(
+            vec![time!(01:45:00)],
+            datetime!(2024-04-06 00:00:00 UTC),
+            "Australia/Lord_Howe",
+        )
+        .on_overlap(OverlapPolicy::Twice);
+
+        assert_eq!(
+            iterator
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-04-07 01:45:00 +11)),
+                Ok(datetime!(2024-04-07 01:45:00 +10:30)),
+            ]
+        );
+
+        // Both occurrences of several times are returned in the order of
+        // the instants they refer to.
+        let iterator = daily_in_zone(
+            vec![time!(02:15:00), time!(02:45:00)],
+            datetime!(2024-10-26 12:00:00 UTC),
+            "Europe/Berlin",
+        )
+        .on_overlap(OverlapPolicy::Twice);
+
+        assert_eq!(
+            iterator
+                .take(5)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            vec![
+                Ok(datetime!(2024-10-27 02:15:00 +2)),
+                Ok(datetime!(2024-10-27 02:45:00 +2)),
+                Ok(datetime!(2024-10-27 02:15:00 +1)),
+                Ok(datetime!(2024-10-27 02:45:00 +1)),
+                Ok(datetime!(2024-10-28 02:15:00 +1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule_iter_from() {
+        let schedule = Schedule::from_str("at 6 AM on Mondays").unwrap();
+
+        let result = vec![
+            Ok(datetime!(2020-01-06 06:00:00 -5)),
+            Ok(datetime!(2020-01-13 06:00:00 -5)),
+        ];
+
+        assert_eq!(
+            schedule
+                .iter_from(datetime!(2020-01-01 12:00:00 -5))
+                .take(2)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
 
-            match modifier {
-                WeekVariant::Even => week % 2 == 0,
-                WeekVariant::Odd => week % 2 != 0,
-            }
-        }
-        None => true,
-    };
+        // A zone that is part of the expression takes precedence over the
+        // offset of the start.
+        let schedule = Schedule::from_str("at 6 AM on Mondays +02:00").unwrap();
 
-    is_correct_day && is_correct_week
-}
+        assert_eq!(
+            schedule.iter_from(datetime!(2020-01-01 12:00:00 -5)).next(),
+            Some(Ok(datetime!(2020-01-06 06:00:00 +2)))
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use time::macros::{datetime, offset, time};
-    use time::Weekday;
+        let multi = MultiSchedule::from((Schedule::from_str("at 6 AM on Mondays").unwrap(), 0))
+            + (Schedule::from_str("at 6 PM on Tuesdays").unwrap(), 1);
 
-    #[test]
-    fn test_compute_dates_1() {
-        let base = datetime!(2021-06-04 13:38:00 UTC);
-        let spec = ParsedSchedule {
-            times: vec![time!(12:00:00), time!(18:00:00)],
-            days: None,
-            weeks: None,
-        };
         let result = vec![
-            datetime!(2021-06-11 12:00:00 UTC),
-            datetime!(2021-06-05 12:00:00 UTC),
-            datetime!(2021-06-06 12:00:00 UTC),
-            datetime!(2021-06-07 12:00:00 UTC),
-            datetime!(2021-06-08 12:00:00 UTC),
-            datetime!(2021-06-09 12:00:00 UTC),
-            datetime!(2021-06-10 12:00:00 UTC),
-            datetime!(2021-06-04 18:00:00 UTC),
-            datetime!(2021-06-05 18:00:00 UTC),
-            datetime!(2021-06-06 18:00:00 UTC),
-            datetime!(2021-06-07 18:00:00 UTC),
-            datetime!(2021-06-08 18:00:00 UTC),
-            datetime!(2021-06-09 18:00:00 UTC),
-            datetime!(2021-06-10 18:00:00 UTC),
+            Ok((datetime!(2020-01-06 06:00:00 UTC), &0)),
+            Ok((datetime!(2020-01-07 18:00:00 UTC), &1)),
+            Ok((datetime!(2020-01-13 06:00:00 UTC), &0)),
         ];
-        assert_eq!(compute_dates(base, &spec), result);
+
+        assert_eq!(
+            multi
+                .iter_from(datetime!(2020-01-01 12:00:00 UTC))
+                .take(3)
+                .collect::<Vec<Result<(OffsetDateTime, &usize), Error>>>(),
+            result
+        );
     }
 
     #[test]
-    fn test_compute_dates_2() {
-        let base = datetime!(2021-06-04 13:38:00 UTC);
-        let spec = ParsedSchedule {
-            times: vec![time!(18:00:00)],
-            days: Some(vec![(Weekday::Monday, None), (Weekday::Thursday, None)]),
-            weeks: None,
-        };
+    fn test_schedule_iter_utc() {
+        let schedule = Schedule::from_str("at every full hour").unwrap();
+        let now = OffsetDateTime::now_utc();
+
+        let next = schedule.iter_utc().next().unwrap().unwrap();
+        assert_eq!(next.offset(), UtcOffset::UTC);
+        assert!(next > now);
+
+        let multi = MultiSchedule::from((schedule, "label"));
+        let (next, label) = multi.iter_utc().next().unwrap().unwrap();
+        assert_eq!(*label, "label");
+        assert_eq!(next.offset(), UtcOffset::UTC);
+        assert!(next > now);
+    }
+
+    #[test]
+    fn test_schedule_rev_iteration() {
+        let expressions = [
+            "at 6 PM on the last Monday in odd weeks",
+            "at 6 AM on the second-to-last Friday in January, June and December",
+            "every 15 minutes between 9 AM and 10 AM on weekdays",
+            "at 6 PM on the 31st of the month or the last day",
+            "at 6 PM on the last business day of the month except on public holidays",
+            "at 9 AM on the 1st of the month or the following business day",
+            "at 6 AM on Mondays every 3rd week starting 2024-01-01",
+            "at 10 AM on Thursdays in weeks 10 to 20",
+            "at every full hour on Sundays in even weeks",
+        ];
+
+        let holidays = [
+            date!(2024 - 05 - 31),
+            date!(2024 - 12 - 25),
+            date!(2024 - 12 - 26),
+            date!(2025 - 01 - 01),
+        ];
+
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+
+            let forward = schedule
+                .iter_from(datetime!(2024-01-01 00:00:00 UTC))
+                .holidays("public holidays", holidays)
+                .take(10)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>();
+
+            let mut backward = schedule
+                .rev_iter_from(*forward[9].as_ref().unwrap())
+                .holidays("public holidays", holidays)
+                .take(9)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>();
+
+            backward.reverse();
+            assert_eq!(backward, forward[..9], "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_schedule_prev() {
+        let schedule = Schedule::from_str("at 6 AM on the last Monday").unwrap();
+        let mut iter = schedule.iter_from(datetime!(2024-01-01 12:00:00 UTC));
+
+        assert_eq!(iter.prev(), Some(Ok(datetime!(2023-12-25 06:00:00 UTC))));
+        assert_eq!(iter.next(), Some(Ok(datetime!(2024-01-29 06:00:00 UTC))));
+
+        // A schedule that never matches has no previous date either.
+        let schedule =
+            Schedule::from_str("at 6 AM on the fifth Friday in February in week 1").unwrap();
+        assert_eq!(
+            schedule
+                .rev_iter_from(datetime!(2024-01-01 12:00:00 UTC))
+                .next(),
+            None
+        );
+
+        let multi = MultiSchedule::from((Schedule::from_str("at 6 AM on Mondays").unwrap(), 0))
+            + (Schedule::from_str("at 6 PM on Tuesdays").unwrap(), 1);
+
         let result = vec![
-            datetime!(2021-06-07 18:00:00 UTC),
-            datetime!(2021-06-10 18:00:00 UTC),
+            Ok((datetime!(2019-12-31 18:00:00 UTC), &1)),
+            Ok((datetime!(2019-12-30 06:00:00 UTC), &0)),
+            Ok((datetime!(2019-12-24 18:00:00 UTC), &1)),
         ];
-        assert_eq!(compute_dates(base, &spec), result);
+
+        assert_eq!(
+            multi
+                .rev_iter_from(datetime!(2020-01-01 12:00:00 UTC))
+                .take(3)
+                .collect::<Vec<Result<(OffsetDateTime, &usize), Error>>>(),
+            result
+        );
     }
 
     #[test]
-    fn test_compute_dates_3() {
-        let base = datetime!(2021-06-04 13:38:00 UTC);
-        let spec = ParsedSchedule {
-            times: vec![time!(18:00:00)],
-            days: Some(vec![
-                (Weekday::Monday, Some(WeekdayModifier::Second)),
-                (Weekday::Thursday, None),
-            ]),
-            weeks: None,
-        };
+    #[cfg(feature = "tz")]
+    fn test_schedule_rev_iteration_dst_transitions() {
+        let schedule = Schedule::from_str("every 30 minutes between 1 AM and 4 AM").unwrap();
+        let zone = TimeZone::named("Europe/Berlin").unwrap();
+
+        for start in [
+            datetime!(2024-03-30 12:00:00 UTC),
+            datetime!(2024-10-26 12:00:00 UTC),
+        ] {
+            let forward = schedule
+                .iter_from(start)
+                .time_zone(zone.clone())
+                .on_overlap(OverlapPolicy::Twice)
+                .take(12)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>();
+
+            let mut backward = schedule
+                .rev_iter_from(*forward[11].as_ref().unwrap())
+                .time_zone(zone.clone())
+                .on_overlap(OverlapPolicy::Twice)
+                .take(11)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>();
+
+            backward.reverse();
+            assert_eq!(backward, forward[..11]);
+        }
+    }
+
+    #[test]
+    fn test_schedule_occurrences_between() {
+        let schedule = Schedule::from_str("at 6 AM and 6 PM on Mondays").unwrap();
+
         let result = vec![
-            datetime!(2021-06-14 18:00:00 UTC),
-            datetime!(2021-06-10 18:00:00 UTC),
+            Ok(datetime!(2024-01-01 06:00:00 UTC)),
+            Ok(datetime!(2024-01-01 18:00:00 UTC)),
+            Ok(datetime!(2024-01-08 06:00:00 UTC)),
+        ];
+
+        assert_eq!(
+            schedule
+                .occurrences_between(
+                    datetime!(2024-01-01 06:00:00 UTC),
+                    datetime!(2024-01-08 18:00:00 UTC)
+                )
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+
+        let schedule = Schedule::from_str("at every full hour").unwrap();
+        assert_eq!(
+            schedule.count_between(
+                datetime!(2024-01-01 00:00:00 UTC),
+                datetime!(2025-01-01 00:00:00 UTC)
+            ),
+            Ok(8784)
+        );
+    }
+
+    #[test]
+    fn test_schedule_count_between() {
+        let expressions = [
+            "at 6 AM, 6 PM and 11:59 PM on the last Monday in odd weeks",
+            "every 15 minutes between 9 AM and 10 AM on weekdays except on public holidays",
+            "every 7 minutes between 8 AM and 5 PM on the last business day of the month",
+            "at every full hour on the 1st of the month or the following business day",
         ];
-        assert_eq!(compute_dates(base, &spec), result);
+
+        let start = datetime!(2024-01-03 12:00:00 UTC);
+        let end = datetime!(2024-12-31 09:30:00 UTC);
+
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+
+            let iter = schedule
+                .occurrences_between(start, end)
+                .holidays("public holidays", [date!(2024 - 12 - 25)]);
+
+            assert_eq!(
+                iter.clone().count_dates(),
+                Ok(iter.count()),
+                "{}",
+                expression
+            );
+        }
+
+        // Dates shared by several schedules are counted once for each of them.
+        let multi = Schedule::from_str("every 30 minutes between 8 AM and 6 PM on weekdays")
+            .unwrap()
+            + Schedule::from_str("at every full hour on Mondays").unwrap();
+
+        assert_eq!(
+            multi.count_between(start, end),
+            Ok(multi.occurrences_between(start, end).count())
+        );
     }
 
     #[test]
-    fn test_compute_dates_4() {
-        let base = datetime!(2021-06-04 13:38:00 UTC);
-        let spec = ParsedSchedule {
-            times: vec![time!(12:00:00), time!(18:00:00)],
-            days: Some(vec![
-                (Weekday::Friday, Some(WeekdayModifier::First)),
-                (Weekday::Thursday, None),
-            ]),
-            weeks: None,
-        };
+    #[cfg(feature = "tz")]
+    fn test_schedule_count_between_dst_transitions() {
+        let schedule = Schedule::from_str("every 30 minutes between 1 AM and 4 AM").unwrap();
+        let zone = TimeZone::named("Europe/Berlin").unwrap();
+
+        for overlap in [OverlapPolicy::Earliest, OverlapPolicy::Twice] {
+            let iter = schedule
+                .occurrences_between(
+                    datetime!(2024-01-01 00:00:00 UTC),
+                    datetime!(2025-01-01 00:00:00 UTC),
+                )
+                .time_zone(zone.clone())
+                .on_overlap(overlap);
+
+            assert_eq!(iter.clone().count_dates(), Ok(iter.count()));
+        }
+    }
+
+    #[test]
+    fn test_schedule_matches() {
+        let expressions = [
+            "at 6 PM on the last Monday in odd weeks",
+            "at 6 AM and 11 PM on the second-to-last Friday from March to October",
+            "every 20 minutes between 9 AM and 5 PM on weekdays except on public holidays",
+            "at 6 PM on the 31st of the month or the last day",
+            "at 6 PM on the first business day of the month",
+            "at 9 AM on the 1st of the month or the modified following business day",
+            "at every full hour on Mondays every 3rd week starting 2024-01-01",
+        ];
+
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let end = datetime!(2024-07-01 00:00:00 UTC);
+
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let iter = schedule
+                .occurrences_between(start, end)
+                .holidays("public holidays", [date!(2024 - 05 - 01)]);
+
+            let dates = iter
+                .clone()
+                .collect::<Result<BTreeSet<OffsetDateTime>, Error>>()
+                .unwrap();
+
+            // Compare every 20 minutes of the range with the dates of the
+            // schedule.
+            let mut instant = start;
+
+            while instant < end {
+                assert_eq!(
+                    iter.matches(instant),
+                    Ok(dates.contains(&instant)),
+                    "{} at {}",
+                    expression,
+                    instant
+                );
+                instant += Duration::minutes(20);
+            }
+        }
+
+        let schedule = Schedule::from_str("at 6 AM on Mondays except on public holidays").unwrap();
+        assert!(!schedule.matches(datetime!(2024-03-04 06:00:00 UTC)));
+        assert_eq!(
+            schedule
+                .iter_from(start)
+                .matches(datetime!(2024-03-04 06:00:00 UTC)),
+            Err(Error::UnknownCalendar("public holidays".to_string()))
+        );
+
+        let multi = MultiSchedule::from((Schedule::from_str("at 6 AM on Mondays").unwrap(), 0))
+            + (Schedule::from_str("at 6 AM on weekdays +02:00").unwrap(), 1);
+
+        assert_eq!(multi.matches(datetime!(2024-03-04 06:00:00 UTC)), Some(&0));
+        assert_eq!(multi.matches(datetime!(2024-03-05 06:00:00 +2)), Some(&1));
+        assert_eq!(multi.matches(datetime!(2024-03-05 06:00:00 UTC)), None);
+
+        // Without the calendar the schedule that refers to it never matches,
+        // while the iterator reports the missing calendar.
+        let multi = MultiSchedule::from((schedule, 0))
+            + (Schedule::from_str("at 6 AM on Mondays").unwrap(), 1);
+        let monday = datetime!(2024-03-04 06:00:00 UTC);
+        assert_eq!(multi.matches(monday), Some(&1));
+        assert_eq!(
+            multi.iter_from(start).matches(monday),
+            Err(Error::UnknownCalendar("public holidays".to_string()))
+        );
+        assert_eq!(
+            multi
+                .iter_from(start)
+                .holidays("public holidays", [date!(2024 - 05 - 01)])
+                .matches(monday),
+            Ok(Some(&0))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn test_schedule_matches_dst_transitions() {
+        let schedule = Schedule::from_str("every 30 minutes between 1 AM and 4 AM").unwrap();
+        let zone = TimeZone::named("Europe/Berlin").unwrap();
+
+        for gap in [GapPolicy::Skip, GapPolicy::ShiftForward] {
+            for overlap in [
+                OverlapPolicy::Earliest,
+                OverlapPolicy::Latest,
+                OverlapPolicy::Twice,
+            ] {
+                for start in [
+                    datetime!(2024-03-30 12:00:00 UTC),
+                    datetime!(2024-10-26 12:00:00 UTC),
+                ] {
+                    let end = start + Duration::days(2);
+                    let iter = schedule
+                        .occurrences_between(start, end)
+                        .time_zone(zone.clone())
+                        .on_gap(gap)
+                        .on_overlap(overlap);
+
+                    let dates = iter
+                        .clone()
+                        .collect::<Result<Vec<OffsetDateTime>, Error>>()
+                        .unwrap();
+
+                    let mut instant = start;
+
+                    while instant < end {
+                        assert_eq!(iter.matches(instant), Ok(dates.contains(&instant)));
+                        instant += Duration::minutes(30);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedule_iteration_repeated_weekday() {
+        let schedule =
+            Schedule::from_str("at 6 AM on the first Monday and the third Monday").unwrap();
+
         let result = vec![
-            datetime!(2021-07-02 12:00:00 UTC),
-            datetime!(2021-06-10 12:00:00 UTC),
-            datetime!(2021-06-04 18:00:00 UTC),
-            datetime!(2021-06-10 18:00:00 UTC),
+            Ok(datetime!(2024-01-01 06:00:00 UTC)),
+            Ok(datetime!(2024-01-15 06:00:00 UTC)),
+            Ok(datetime!(2024-02-05 06:00:00 UTC)),
         ];
-        assert_eq!(compute_dates(base, &spec), result);
+
+        assert_eq!(
+            schedule
+                .iter_from(datetime!(2023-12-31 12:00:00 UTC))
+                .take(3)
+                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
+            result
+        );
+        assert!(schedule.matches(datetime!(2024-01-15 06:00:00 UTC)));
     }
 
     #[test]
-    fn test_compute_dates_5() {
-        let base = datetime!(2021-06-12 13:38:00 UTC);
-        let spec = ParsedSchedule {
-            times: vec![time!(06:00:00), time!(12:00:00), time!(18:00:00)],
-            days: Some(vec![
-                (Weekday::Friday, Some(WeekdayModifier::First)),
-                (Weekday::Thursday, None),
-                (Weekday::Monday, Some(WeekdayModifier::Third)),
-            ]),
-            weeks: None,
-        };
+    fn test_schedule_iteration_many_schedules() {
+        let expressions = (0..200).map(|i| match i % 4 {
+            0 => format!("at {}:{:02} on Mondays", i % 24, i % 60),
+            1 => format!(
+                "every {} minutes between 9 AM and 5 PM on weekdays",
+                i % 50 + 10
+            ),
+            2 => format!("at 6 PM on the {}th of the month", i % 17 + 4),
+            _ => "at 9 AM on Mondays".to_string(),
+        });
+
+        // Label each schedule with its index.
+        let multi = expressions
+            .map(|expression| Schedule::from_str(&expression).unwrap())
+            .enumerate()
+            .fold(
+                None,
+                |multi: Option<MultiSchedule<usize>>, (i, schedule)| match multi {
+                    None => Some((schedule, i).into()),
+                    Some(multi) => Some(multi + (schedule, i)),
+                },
+            )
+            .unwrap();
+
+        let MultiSchedule(ref schedules, _) = multi;
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let mut position: Option<(OffsetDateTime, usize)> = None;
+
+        for (date, index) in multi.iter_from(start).take(500).map(|item| item.unwrap()) {
+            // Compare with the earliest date of all schedules that follows
+            // the date and schedule that were returned last.
+            let expected = schedules
+                .iter()
+                .enumerate()
+                .filter_map(|(i, spec)| {
+                    let next = |base| next_date(base, spec, &HashMap::new(), None).unwrap();
+
+                    match position {
+                        None => next(start),
+                        Some((current, last)) => match next(current - Duration::NANOSECOND) {
+                            Some(d) if (d, i) <= (current, last) => next(current),
+                            other => other,
+                        },
+                    }
+                    .map(|d| (d, i))
+                })
+                .min()
+                .unwrap();
+
+            assert_eq!((date, *index), expected);
+            position = Some(expected);
+        }
+
+        // Changing the iterator after it started computes the upcoming
+        // dates again.
+        let mut iter = multi.iter_from(start);
+        let (first, _) = iter.next().unwrap().unwrap();
+        let mut iter = iter.assume_offset(offset!(+1));
+
+        assert_eq!(
+            iter.next(),
+            multi.iter_from(first).assume_offset(offset!(+1)).next()
+        );
+    }
+
+    #[test]
+    fn test_schedule_labels() {
+        let mut jobs = MultiSchedule::from((
+            Schedule::from_str("at 6 AM on Mondays").unwrap(),
+            "monday".to_string(),
+        ));
+        jobs += (
+            Schedule::from_str("at 6 AM on weekdays").unwrap(),
+            "weekday".to_string(),
+        );
+        let jobs = jobs
+            + MultiSchedule::from((
+                Schedule::from_str("at 6 PM on Fridays").unwrap(),
+                "friday".to_string(),
+            ));
+
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let labels = jobs
+            .iter_from(start)
+            .take(7)
+            .map(|item| item.unwrap().1)
+            .collect::<Vec<&String>>();
+
+        // Dates that several schedules share come once with the label of
+        // each of them.
+        assert_eq!(
+            labels,
+            vec!["monday", "weekday", "weekday", "weekday", "weekday", "weekday", "friday"]
+        );
+
+        let mut iter = jobs.iter_from(datetime!(2024-01-06 00:00:00 UTC));
+        assert_eq!(
+            iter.prev(),
+            Some(Ok((
+                datetime!(2024-01-05 18:00:00 UTC),
+                &"friday".to_string()
+            )))
+        );
+        assert_eq!(
+            iter.matches(datetime!(2024-01-03 06:00:00 UTC)),
+            Ok(Some(&"weekday".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_schedule_labels_shared_dates() {
+        let jobs = MultiSchedule::from((Schedule::from_str("at 2 AM").unwrap(), "backup"))
+            + (Schedule::from_str("at 2 AM on Mondays").unwrap(), "report");
+
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let mut iter = jobs.iter_from(start);
+
         let result = vec![
-            datetime!(2021-06-21 06:00:00 UTC),
-            datetime!(2021-06-17 06:00:00 UTC),
-            datetime!(2021-07-02 06:00:00 UTC),
-            datetime!(2021-06-21 12:00:00 UTC),
-            datetime!(2021-06-17 12:00:00 UTC),
-            datetime!(2021-07-02 12:00:00 UTC),
-            datetime!(2021-06-21 18:00:00 UTC),
-            datetime!(2021-06-17 18:00:00 UTC),
-            datetime!(2021-07-02 18:00:00 UTC),
+            Ok((datetime!(2024-01-01 02:00:00 UTC), &"backup")),
+            Ok((datetime!(2024-01-01 02:00:00 UTC), &"report")),
+            Ok((datetime!(2024-01-02 02:00:00 UTC), &"backup")),
+        ];
+
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), result);
+
+        // Going back returns the shared date for each schedule again, in
+        // reverse order.
+        assert_eq!(
+            iter.prev(),
+            Some(Ok((datetime!(2024-01-01 02:00:00 UTC), &"report")))
+        );
+        assert_eq!(
+            iter.prev(),
+            Some(Ok((datetime!(2024-01-01 02:00:00 UTC), &"backup")))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(Ok((datetime!(2024-01-01 02:00:00 UTC), &"report")))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(Ok((datetime!(2024-01-02 02:00:00 UTC), &"backup")))
+        );
+
+        // Counting follows the iterator, so that shared dates are counted
+        // once for each schedule.
+        let end = datetime!(2024-01-15 00:00:00 UTC);
+        assert_eq!(jobs.count_between(start, end), Ok(16));
+        assert_eq!(jobs.occurrences_between(start, end).count(), 16);
+
+        let mut iter = jobs.occurrences_between(start, end);
+        iter.next();
+        assert_eq!(iter.count_dates(), Ok(15));
+    }
+
+    #[test]
+    fn test_schedule_into_iter() {
+        let multi = MultiSchedule::from((
+            Schedule::from_str("at 9 AM on Mondays").unwrap(),
+            "monday".to_string(),
+        )) + (
+            Schedule::from_str("at 6 PM on the 4th of the month").unwrap(),
+            "4th".to_string(),
+        );
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let expected: Vec<_> = multi
+            .iter_from(start)
+            .assume_offset(offset!(+1))
+            .take(10)
+            .map(|item| item.map(|(date, label)| (date, label.clone())))
+            .collect();
+
+        // The owned iterator may outlive the MultiSchedule and be sent to
+        // another thread. It returns clones of the labels.
+        let iter = multi
+            .clone()
+            .into_iter_from(start)
+            .assume_offset(offset!(+1));
+        let handle = std::thread::spawn(move || iter.take(10).collect::<Vec<_>>());
+        assert_eq!(handle.join().unwrap(), expected);
+
+        let now = OffsetDateTime::now_utc();
+        for item in multi.into_iter().take(10) {
+            let (date, _) = item.unwrap();
+            assert!(date > now);
+        }
+    }
+
+    #[test]
+    fn test_schedule_iteration_calendar_edges() {
+        let expressions = [
+            "at 6 PM",
+            "at every full hour",
+            "every 15 minutes between 9 AM and 5 PM on weekdays",
+            "at 6 PM on Mondays and Fridays",
+            "at 6 PM on the last Monday in odd weeks",
+            "at 6 PM on the first Friday and the fifth Monday",
+            "at 6 PM on the 31st of the month",
+            "at 6 PM on the 1st and the last day of the month in January and December",
+            "at 6 PM on Fridays in week 53",
+            "at 6 PM on Mondays every 3rd week starting 2024-01-01",
+            "at 6 PM on the last business day of the month",
+            "at 6 PM on the 1st of the month or the following business day",
+            "at 6 PM on the 31st of the month or the preceding business day",
+            "at 6 PM on weekdays except on December 31",
         ];
 
-        assert_eq!(compute_dates(base, &spec), result);
+        let first = PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT).assume_utc();
+        let last = PrimitiveDateTime::new(Date::MAX, Time::MIDNIGHT).assume_utc();
+
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+
+            // Iteration ends with the last representable day.
+            for date in schedule.iter_from(last) {
+                assert_eq!(date.unwrap().date(), Date::MAX, "{}", expression);
+            }
+
+            assert!(schedule.rev_iter_from(last).next().is_some());
+
+            // Iteration starts with the first representable day.
+            let next = schedule.iter_from(first).next().unwrap().unwrap();
+            assert!(next > first, "{}", expression);
+            assert_eq!(schedule.rev_iter_from(first).next(), None);
+            assert_eq!(schedule.rev_iter_from(next).next(), None);
+        }
+
+        let schedule = Schedule::from_str("at 6 PM on Mondays and Fridays").unwrap();
+        assert_eq!(
+            schedule.iter_from(last).collect::<Vec<_>>(),
+            vec![Ok(datetime!(9999-12-31 18:00:00 UTC))]
+        );
+        assert_eq!(
+            schedule.iter_from(first).next(),
+            Some(Ok(datetime!(-9999-01-01 18:00:00 UTC)))
+        );
+        assert_eq!(
+            schedule
+                .rev_iter_from(datetime!(-9999-01-06 00:00:00 UTC))
+                .collect::<Vec<_>>(),
+            vec![
+                Ok(datetime!(-9999-01-05 18:00:00 UTC)),
+                Ok(datetime!(-9999-01-01 18:00:00 UTC))
+            ]
+        );
+
+        let multi =
+            schedule.clone() + Schedule::from_str("at 6 AM on the 31st of the month").unwrap();
+        assert_eq!(
+            multi
+                .iter_from(last)
+                .map(|item| item.map(|(date, _)| date))
+                .collect::<Vec<_>>(),
+            vec![
+                Ok(datetime!(9999-12-31 06:00:00 UTC)),
+                Ok(datetime!(9999-12-31 18:00:00 UTC))
+            ]
+        );
+        assert_eq!(multi.rev_iter_from(first).next(), None);
+        assert_eq!(
+            multi.count_between(first, datetime!(-9999-01-06 00:00:00 UTC)),
+            Ok(2)
+        );
+        assert_eq!(
+            multi.count_between(last, datetime!(9999-12-31 23:59:59 UTC)),
+            Ok(2)
+        );
+
+        // The zone of an expression may move the start beyond the edges.
+        let schedule = Schedule::from_str("at 6 PM -05:00").unwrap();
+        assert_eq!(
+            schedule.iter_from(last).collect::<Vec<_>>(),
+            vec![Ok(datetime!(9999-12-31 18:00:00 -5))]
+        );
+        assert_eq!(
+            schedule.iter_from(first).next(),
+            Some(Ok(datetime!(-9999-01-01 18:00:00 -5)))
+        );
     }
 
     #[test]
-    fn test_schedule_iteration_1() {
-        let iterator = ScheduleIter {
-            current: datetime!(2021-06-09 13:00:00 UTC),
-            schedule: ParsedSchedule {
-                times: vec![time!(01:00:00)],
-                days: None,
-                weeks: None,
-            },
-            skip_outdated: false,
-            offset: None,
-        };
-
-        let result = vec![
-            Ok(datetime!(2021-06-10 01:00:00 UTC)),
-            Ok(datetime!(2021-06-11 01:00:00 UTC)),
-            Ok(datetime!(2021-06-12 01:00:00 UTC)),
-        ];
+    #[cfg(feature = "tz")]
+    fn test_schedule_iteration_time_zone_edges() {
+        // Wall-clock times close to the edges may refer to instants that are
+        // out of the range of the time crate.
+        let schedule = Schedule::from_str("at 11 PM America/New_York").unwrap();
+        assert_eq!(
+            schedule
+                .iter_from(datetime!(9999-12-30 12:00:00 UTC))
+                .collect::<Vec<_>>(),
+            vec![
+                Ok(datetime!(9999-12-30 23:00:00 -5)),
+                Ok(datetime!(9999-12-31 23:00:00 -5))
+            ]
+        );
+        assert!(schedule.matches(datetime!(9999-12-31 23:00:00 -5)));
 
+        let schedule = Schedule::from_str("at 1 AM Asia/Tokyo").unwrap();
+        let zone = TimeZone::named("Asia/Tokyo").unwrap();
+        let offset = zone.offset_at(datetime!(-9999-01-01 00:00:00 UTC));
         assert_eq!(
-            iterator
-                .take(3)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            schedule
+                .rev_iter_from(datetime!(-9999-01-02 12:00:00 UTC))
+                .last(),
+            Some(Ok(datetime!(-9999-01-01 01:00:00).assume_offset(offset)))
         );
     }
 
     #[test]
-    fn test_schedule_iteration_2() {
-        let iterator = ScheduleIter {
-            current: datetime!(2021-06-09 13:00:00 UTC),
+    fn test_schedule_iteration_unknown_calendar() {
+        let mut iterator = ScheduleIter {
+            current: datetime!(2021-06-01 12:00:00 UTC),
             schedule: ParsedSchedule {
-                times: vec![time!(13:00:00)],
-                days: Some(vec![(Weekday::Monday, None)]),
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: None,
+                monthdays: None,
+                business_days: None,
+                months: None,
                 weeks: None,
+                roll: None,
+                exceptions: Some(vec![Exception::Holidays("public holidays".to_string())]),
+                zone: None,
             },
             skip_outdated: false,
-            offset: None,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
         };
 
-        let result = vec![
-            Ok(datetime!(2021-06-14 13:00:00 UTC)),
-            Ok(datetime!(2021-06-21 13:00:00 UTC)),
-            Ok(datetime!(2021-06-28 13:00:00 UTC)),
-        ];
-
         assert_eq!(
-            iterator
-                .take(3)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            iterator.next(),
+            Some(Err(Error::UnknownCalendar("public holidays".to_string())))
         );
     }
 
     #[test]
-    fn test_schedule_iteration_3() {
-        let iterator = ScheduleIter {
-            current: datetime!(2021-06-09 13:00:00 UTC),
+    fn test_schedule_iteration_all_excluded() {
+        let mut iterator = ScheduleIter {
+            current: datetime!(2021-06-01 12:00:00 UTC),
             schedule: ParsedSchedule {
-                times: vec![time!(06:00:00), time!(13:00:00)],
-                days: Some(vec![
-                    (Weekday::Monday, Some(WeekdayModifier::Third)),
-                    (Weekday::Thursday, None),
-                ]),
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![(Weekday::Saturday, None), (Weekday::Sunday, None)]),
+                monthdays: None,
+                business_days: None,
+                months: None,
                 weeks: None,
+                roll: None,
+                exceptions: Some(vec![
+                    Exception::Weekday(Weekday::Saturday),
+                    Exception::Weekday(Weekday::Sunday),
+                ]),
+                zone: None,
             },
             skip_outdated: false,
-            offset: None,
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
         };
 
-        let result = vec![
-            Ok(datetime!(2021-06-10 06:00:00 UTC)),
-            Ok(datetime!(2021-06-10 13:00:00 UTC)),
-            Ok(datetime!(2021-06-17 06:00:00 UTC)),
-            Ok(datetime!(2021-06-17 13:00:00 UTC)),
-            Ok(datetime!(2021-06-21 06:00:00 UTC)),
-            Ok(datetime!(2021-06-21 13:00:00 UTC)),
-            Ok(datetime!(2021-06-24 06:00:00 UTC)),
-            Ok(datetime!(2021-06-24 13:00:00 UTC)),
-        ];
-
-        assert_eq!(
-            iterator
-                .take(8)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
-        );
+        assert_eq!(iterator.next(), None);
     }
 
     #[test]
-    fn test_schedule_iteration_4() {
-        let iterator = ScheduleIter {
-            current: datetime!(2021-06-09 13:00:00 UTC),
+    fn test_schedule_iteration_never_matches() {
+        // February never falls into one of the first three ISO weeks.
+        let mut iterator = ScheduleIter {
+            current: datetime!(2021-06-01 12:00:00 UTC),
             schedule: ParsedSchedule {
-                times: vec![time!(06:00:00), time!(13:00:00)],
-                days: Some(vec![
-                    (Weekday::Monday, Some(WeekdayModifier::Third)),
-                    (Weekday::Thursday, None),
-                ]),
-                weeks: None,
+                times: TimeSpec::List(vec![time!(06:00:00)]),
+                days: Some(vec![(Weekday::Friday, Some(WeekdayModifier::Fifth))]),
+                monthdays: None,
+                business_days: None,
+                months: Some(vec![Month::February]),
+                weeks: Some(WeekVariant::Numbers(vec![1, 2, 3])),
+                roll: None,
+                exceptions: None,
+                zone: None,
             },
             skip_outdated: false,
-            offset: Some(offset!(+3)),
+            zone: None,
+            calendars: HashMap::new(),
+            roll: None,
+            gap: GapPolicy::ShiftForward,
+            overlap: OverlapPolicy::Earliest,
+            reverse: false,
+            end: None,
         };
 
-        let result = vec![
-            Ok(datetime!(2021-06-10 06:00:00 +3)),
-            Ok(datetime!(2021-06-10 13:00:00 +3)),
-            Ok(datetime!(2021-06-17 06:00:00 +3)),
-            Ok(datetime!(2021-06-17 13:00:00 +3)),
-            Ok(datetime!(2021-06-21 06:00:00 +3)),
-            Ok(datetime!(2021-06-21 13:00:00 +3)),
-            Ok(datetime!(2021-06-24 06:00:00 +3)),
-            Ok(datetime!(2021-06-24 13:00:00 +3)),
-        ];
+        assert_eq!(iterator.next(), None);
+    }
 
+    #[test]
+    fn test_next_week_start() {
         assert_eq!(
-            iterator
-                .take(8)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            next_week_start(date!(2021 - 01 - 06), &[1, 52]),
+            Some(date!(2021 - 12 - 29))
+        );
+        assert_eq!(
+            next_week_start(date!(2021 - 12 - 29), &[1, 52]),
+            Some(date!(2022 - 01 - 05))
         );
+        assert_eq!(
+            next_week_start(date!(2021 - 01 - 06), &[53]),
+            Some(date!(2026 - 12 - 30))
+        );
+        assert_eq!(next_week_start(date!(9999 - 12 - 29), &[1]), None);
     }
 
     #[test]
-    fn test_schedule_iteration_5() {
-        let iterator = MultiScheduleIter {
-            current: datetime!(2021-06-09 13:00:00 UTC),
-            schedules: &vec![
-                ParsedSchedule {
-                    times: vec![time!(06:00:00), time!(13:00:00)],
-                    days: Some(vec![
-                        (Weekday::Monday, Some(WeekdayModifier::Third)),
-                        (Weekday::Thursday, None),
-                    ]),
-                    weeks: None,
-                },
-                ParsedSchedule {
-                    times: vec![time!(18:00:00)],
-                    days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
-                    weeks: Some(WeekVariant::Odd),
-                },
-            ],
-            skip_outdated: false,
-            offset: None,
-        };
-
-        let result = vec![
-            Ok(datetime!(2021-06-10 06:00:00 UTC)),
-            Ok(datetime!(2021-06-10 13:00:00 UTC)),
-            Ok(datetime!(2021-06-17 06:00:00 UTC)),
-            Ok(datetime!(2021-06-17 13:00:00 UTC)),
-            Ok(datetime!(2021-06-21 06:00:00 UTC)),
-            Ok(datetime!(2021-06-21 13:00:00 UTC)),
-            Ok(datetime!(2021-06-24 06:00:00 UTC)),
-            Ok(datetime!(2021-06-24 13:00:00 UTC)),
-            Ok(datetime!(2021-06-26 18:00:00 UTC)),
-            Ok(datetime!(2021-07-01 06:00:00 UTC)),
-            Ok(datetime!(2021-07-01 13:00:00 UTC)),
-        ];
-
+    fn test_weeks_between() {
         assert_eq!(
-            iterator
-                .take(11)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            weeks_between(date!(2024 - 01 - 03), date!(2024 - 01 - 01)),
+            0
+        );
+        assert_eq!(
+            weeks_between(date!(2024 - 01 - 03), date!(2024 - 01 - 08)),
+            1
+        );
+        assert_eq!(
+            weeks_between(date!(2024 - 01 - 03), date!(2023 - 12 - 31)),
+            -1
         );
     }
 
     #[test]
-    fn test_schedule_iteration_6() {
-        let iterator = MultiScheduleIter {
-            current: datetime!(2021-06-18 13:00:00 UTC),
-            schedules: &vec![
-                ParsedSchedule {
-                    times: vec![time!(06:00:00), time!(18:00:00)],
-                    days: Some(vec![
-                        (Weekday::Monday, Some(WeekdayModifier::Last)),
-                        (Weekday::Thursday, None),
-                    ]),
-                    weeks: None,
-                },
-                ParsedSchedule {
-                    times: vec![time!(18:00:00)],
-                    days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
-                    weeks: None,
-                },
-            ],
-            skip_outdated: false,
-            offset: None,
-        };
-
-        let result = vec![
-            Ok(datetime!(2021-06-24 06:00:00 UTC)),
-            Ok(datetime!(2021-06-24 18:00:00 UTC)),
-            Ok(datetime!(2021-06-26 18:00:00 UTC)),
-            Ok(datetime!(2021-06-28 06:00:00 UTC)),
-            Ok(datetime!(2021-06-28 18:00:00 UTC)),
-            Ok(datetime!(2021-07-01 06:00:00 UTC)),
-            Ok(datetime!(2021-07-01 18:00:00 UTC)),
-            Ok(datetime!(2021-07-08 06:00:00 UTC)),
-            Ok(datetime!(2021-07-08 18:00:00 UTC)),
-            Ok(datetime!(2021-07-15 06:00:00 UTC)),
-            Ok(datetime!(2021-07-15 18:00:00 UTC)),
-            Ok(datetime!(2021-07-22 06:00:00 UTC)),
-            Ok(datetime!(2021-07-22 18:00:00 UTC)),
-            Ok(datetime!(2021-07-24 18:00:00 UTC)),
-            Ok(datetime!(2021-07-26 06:00:00 UTC)),
-            Ok(datetime!(2021-07-26 18:00:00 UTC)),
-            Ok(datetime!(2021-07-29 06:00:00 UTC)),
-            Ok(datetime!(2021-07-29 18:00:00 UTC)),
-        ];
-
+    fn test_next_monthday() {
         assert_eq!(
-            iterator
-                .take(18)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            next_monthday(date!(2021 - 02 - 10), MonthDay::Day(31), false),
+            Some(date!(2021 - 03 - 31))
+        );
+        assert_eq!(
+            next_monthday(date!(2021 - 02 - 10), MonthDay::Day(31), true),
+            Some(date!(2021 - 02 - 28))
+        );
+        assert_eq!(
+            next_monthday(date!(2021 - 12 - 31), MonthDay::Last, false),
+            Some(date!(2021 - 12 - 31))
+        );
+        assert_eq!(
+            next_monthday(date!(2021 - 12 - 10), MonthDay::Day(5), false),
+            Some(date!(2022 - 01 - 05))
         );
+        assert_eq!(next_monthday(Date::MAX, MonthDay::Day(5), false), None);
     }
 
     #[test]
-    fn test_schedule_iteration_7() {
-        let iterator = MultiScheduleIter {
-            current: datetime!(2021-06-18 13:00:00 UTC),
-            schedules: &vec![
-                ParsedSchedule {
-                    times: vec![time!(06:00:00), time!(18:00:00)],
-                    days: Some(vec![
-                        (Weekday::Monday, Some(WeekdayModifier::Last)),
-                        (Weekday::Thursday, None),
-                    ]),
-                    weeks: None,
-                },
-                ParsedSchedule {
-                    times: vec![time!(18:00:00)],
-                    days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
-                    weeks: None,
-                },
-            ],
-            skip_outdated: false,
-            offset: Some(offset!(+2:30)),
+    fn test_next_time() {
+        let times = TimeSpec::Interval {
+            step: Duration::hours(7),
+            start: time!(00:00:00),
+            end: time!(23:59:00),
         };
+        assert_eq!(
+            next_time(&times, Some(time!(13:00:00))),
+            Some(time!(14:00:00))
+        );
+        assert_eq!(next_time(&times, Some(time!(21:00:00))), None);
+        assert_eq!(next_time(&times, None), Some(time!(00:00:00)));
 
-        let result = vec![
-            Ok(datetime!(2021-06-24 06:00:00 +2:30)),
-            Ok(datetime!(2021-06-24 18:00:00 +2:30)),
-            Ok(datetime!(2021-06-26 18:00:00 +2:30)),
-            Ok(datetime!(2021-06-28 06:00:00 +2:30)),
-            Ok(datetime!(2021-06-28 18:00:00 +2:30)),
-            Ok(datetime!(2021-07-01 06:00:00 +2:30)),
-            Ok(datetime!(2021-07-01 18:00:00 +2:30)),
-        ];
+        let times = TimeSpec::List(vec![time!(18:00:00), time!(06:00:00)]);
+        assert_eq!(
+            next_time(&times, Some(time!(06:00:00))),
+            Some(time!(18:00:00))
+        );
+        assert_eq!(next_time(&times, None), Some(time!(06:00:00)));
+    }
 
+    #[test]
+    fn test_next_weekday() {
+        let date = date!(2024 - 03 - 05);
         assert_eq!(
-            iterator
-                .take(7)
-                .collect::<Vec<Result<OffsetDateTime, Error>>>(),
-            result
+            next_weekday(date, Weekday::Monday, None),
+            Some(date!(2024 - 03 - 11))
+        );
+        assert_eq!(
+            next_weekday(date, Weekday::Tuesday, Some(WeekdayModifier::First)),
+            Some(date!(2024 - 03 - 05))
+        );
+        assert_eq!(
+            next_weekday(date, Weekday::Monday, Some(WeekdayModifier::First)),
+            Some(date!(2024 - 04 - 01))
+        );
+        assert_eq!(
+            next_weekday(date, Weekday::Friday, Some(WeekdayModifier::Fifth)),
+            Some(date!(2024 - 03 - 29))
+        );
+        assert_eq!(
+            next_weekday(date, Weekday::Monday, Some(WeekdayModifier::Fifth)),
+            Some(date!(2024 - 04 - 29))
+        );
+        assert_eq!(
+            next_weekday(date, Weekday::Sunday, Some(WeekdayModifier::SecondToLast)),
+            Some(date!(2024 - 03 - 24))
+        );
+        assert_eq!(next_weekday(Date::MAX, Weekday::Monday, None), None);
+        assert_eq!(
+            next_weekday(Date::MAX, Weekday::Friday, Some(WeekdayModifier::First)),
+            None
         );
     }
 
     #[test]
     fn test_add_two_schedules() {
         let sched1 = Schedule(ParsedSchedule {
-            times: vec![time!(06:00:00), time!(13:00:00)],
+            times: TimeSpec::List(vec![time!(06:00:00), time!(13:00:00)]),
             days: Some(vec![
                 (Weekday::Monday, Some(WeekdayModifier::Third)),
                 (Weekday::Thursday, None),
             ]),
+            monthdays: None,
+            business_days: None,
+            months: None,
             weeks: None,
+            roll: None,
+            exceptions: None,
+            zone: None,
         });
 
         let sched2 = Schedule(ParsedSchedule {
-            times: vec![time!(18:00:00)],
+            times: TimeSpec::List(vec![time!(18:00:00)]),
             days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
+            monthdays: None,
+            business_days: None,
+            months: None,
             weeks: Some(WeekVariant::Odd),
+            roll: None,
+            exceptions: None,
+            zone: None,
         });
 
-        let multi_sched = MultiSchedule(vec![
-            ParsedSchedule {
-                times: vec![time!(06:00:00), time!(13:00:00)],
-                days: Some(vec![
-                    (Weekday::Monday, Some(WeekdayModifier::Third)),
-                    (Weekday::Thursday, None),
-                ]),
-                weeks: None,
-            },
-            ParsedSchedule {
-                times: vec![time!(18:00:00)],
-                days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
-                weeks: Some(WeekVariant::Odd),
-            },
-        ]);
+        let multi_sched = MultiSchedule(
+            vec![
+                ParsedSchedule {
+                    times: TimeSpec::List(vec![time!(06:00:00), time!(13:00:00)]),
+                    days: Some(vec![
+                        (Weekday::Monday, Some(WeekdayModifier::Third)),
+                        (Weekday::Thursday, None),
+                    ]),
+                    monthdays: None,
+                    business_days: None,
+                    months: None,
+                    weeks: None,
+                    roll: None,
+                    exceptions: None,
+                    zone: None,
+                },
+                ParsedSchedule {
+                    times: TimeSpec::List(vec![time!(18:00:00)]),
+                    days: Some(vec![(Weekday::Saturday, Some(WeekdayModifier::Fourth))]),
+                    monthdays: None,
+                    business_days: None,
+                    months: None,
+                    weeks: Some(WeekVariant::Odd),
+                    roll: None,
+                    exceptions: None,
+                    zone: None,
+                },
+            ],
+            vec![(), ()],
+        );
 
         assert_eq!(multi_sched, sched1 + sched2)
     }
+
+    #[test]
+    fn test_multi_schedule_members() {
+        let backup = Schedule::from_str("at 2 AM on Sundays").unwrap();
+        let report = Schedule::from_str("at 6 PM on weekdays").unwrap();
+        let cleanup = Schedule::from_str("at 18:00 on weekdays").unwrap();
+
+        let mut jobs = MultiSchedule::from((backup.clone(), "backup"))
+            + (report.clone(), "report")
+            + (cleanup.clone(), "cleanup")
+            + (backup.clone(), "backup");
+
+        assert_eq!(jobs.len(), 4);
+        assert_eq!(
+            jobs.iter_schedules().collect::<Vec<_>>(),
+            vec![
+                (backup.clone(), &"backup"),
+                (report.clone(), &"report"),
+                (cleanup.clone(), &"cleanup"),
+                (backup.clone(), &"backup"),
+            ]
+        );
+
+        assert_eq!(jobs.remove(1), (report.clone(), "report"));
+        assert_eq!(jobs.remove_label(&"backup"), vec![backup.clone(); 2]);
+        assert_eq!(jobs.remove_label(&"backup"), vec![]);
+        assert_eq!(jobs.len(), 1);
+
+        let mut jobs = jobs + (report.clone(), "report") + (backup.clone(), "backup");
+        jobs.retain(|label| *label != "cleanup");
+        assert_eq!(
+            jobs.clone().into_schedules(),
+            vec![(report.clone(), "report"), (backup.clone(), "backup")]
+        );
+
+        // Schedules are compared by their specification, so "at 18:00"
+        // removes "at 6 PM".
+        let jobs = jobs - cleanup;
+        assert_eq!(jobs.into_schedules(), vec![(backup.clone(), "backup")]);
+
+        let mut multi = backup.clone() + report;
+        multi -= backup;
+        assert_eq!(multi.len(), 1);
+        multi.remove(0);
+        assert!(multi.is_empty());
+
+        // An empty combination does not produce any dates.
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let end = datetime!(2025-01-01 00:00:00 UTC);
+        assert_eq!(multi.iter_from(start).next(), None);
+        assert_eq!(multi.iter_from(start).prev(), None);
+        assert_eq!(multi.count_between(start, end), Ok(0));
+        assert_eq!(multi.matches(start), None);
+    }
 }
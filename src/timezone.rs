@@ -0,0 +1,352 @@
+//! Time zones from the tz database that allow to compute dates in the
+//! wall-clock time of a region, including its daylight saving time
+//! transitions. Requires the `tz` feature.
+use crate::error::*;
+use crate::types::LocalDateTime;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::Arc;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use tz::datetime::{DateTime, FoundDateTimeKind};
+use tz::timezone::TransitionRule;
+use tz::LocalTimeType;
+
+/// A time zone from the tz database, e.g. "Europe/Berlin".
+///
+/// ```rust,no_run
+/// use cron_lingo::timezone::TimeZone;
+/// use cron_lingo::Schedule;
+/// use std::str::FromStr;
+///
+/// fn main() -> Result<(), cron_lingo::error::Error> {
+///     let schedule = Schedule::from_str("at 6 AM on Mondays")?;
+///     let zone = TimeZone::named("Europe/Berlin")?;
+///
+///     // Dates are computed at 6 AM Berlin time, no matter if daylight
+///     // saving time is in effect.
+///     assert!(schedule.iter()?.time_zone(zone).next().is_some());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TimeZone {
+    name: String,
+    inner: Arc<tz::TimeZone>,
+}
+
+impl TimeZone {
+    /// Load a time zone by its name from the zoneinfo files of the system,
+    /// which are usually located in `/usr/share/zoneinfo`.
+    pub fn named(name: &str) -> Result<TimeZone, Error> {
+        // Only accept plain names in order to stay within the zoneinfo
+        // directories.
+        let is_valid = !name.is_empty()
+            && !name.split('/').any(|part| part.is_empty() || part == "..")
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "/_-+".contains(c));
+
+        if !is_valid {
+            return Err(Error::UnknownTimeZone(name.to_string()));
+        }
+
+        let inner = tz::TimeZone::from_posix_tz(&format!(":{}", name))
+            .map_err(|_| Error::UnknownTimeZone(name.to_string()))?;
+
+        Ok(TimeZone {
+            name: name.to_string(),
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Create a time zone from the content of a TZif file, e.g. in order to
+    /// bundle time zones with an application.
+    pub fn from_tz_data(name: &str, bytes: &[u8]) -> Result<TimeZone, Error> {
+        let inner = tz::TimeZone::from_tz_data(bytes)
+            .map_err(|_| Error::UnknownTimeZone(name.to_string()))?;
+
+        Ok(TimeZone {
+            name: name.to_string(),
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// The UTC time zone.
+    pub fn utc() -> TimeZone {
+        TimeZone {
+            name: "UTC".to_string(),
+            inner: Arc::new(tz::TimeZone::utc()),
+        }
+    }
+
+    /// The name of the time zone.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Returns the offset from UTC that is in effect at some instant.
+    pub(crate) fn offset_at(&self, instant: OffsetDateTime) -> UtcOffset {
+        let local_time_type = self
+            .inner
+            .find_local_time_type(instant.unix_timestamp())
+            .unwrap_or_else(|_| self.last_local_time_type());
+
+        to_utc_offset(local_time_type)
+    }
+
+    // Returns the local time type of the last transition. It stays in effect
+    // beyond the range of the time zone data if the data has no rule for the
+    // time after the last transition.
+    fn last_local_time_type(&self) -> &LocalTimeType {
+        let zone = tz::TimeZone::as_ref(&self.inner);
+        let index = zone
+            .transitions()
+            .last()
+            .map_or(0, |transition| transition.local_time_type_index());
+
+        &zone.local_time_types()[index]
+    }
+
+    // Returns the difference between the largest and the smallest offset of
+    // the time zone, which bounds the amount of time by which the offset
+    // changes at a single transition.
+    pub(crate) fn max_transition(&self) -> Duration {
+        let zone = tz::TimeZone::as_ref(&self.inner);
+        let mut offsets: Vec<i32> = zone
+            .local_time_types()
+            .iter()
+            .map(|t| t.ut_offset())
+            .collect();
+
+        match zone.extra_rule() {
+            Some(TransitionRule::Fixed(t)) => offsets.push(t.ut_offset()),
+            Some(TransitionRule::Alternate(rule)) => {
+                offsets.push(rule.std().ut_offset());
+                offsets.push(rule.dst().ut_offset());
+            }
+            None => {}
+        }
+
+        let max = offsets.iter().max().copied().unwrap_or(0);
+        let min = offsets.iter().min().copied().unwrap_or(0);
+
+        Duration::seconds((max - min) as i64)
+    }
+
+    // Maps a wall-clock time to the instant(s) it refers to in this time zone.
+    pub(crate) fn resolve(&self, local: PrimitiveDateTime) -> LocalDateTime {
+        let found = DateTime::find(
+            local.year(),
+            local.month() as u8,
+            local.day(),
+            local.hour(),
+            local.minute(),
+            local.second(),
+            local.nanosecond(),
+            tz::TimeZone::as_ref(&self.inner),
+        )
+        .map(|list| list.into_inner())
+        .unwrap_or_default();
+
+        let resolved = match found.as_slice() {
+            [FoundDateTimeKind::Normal(date)] => {
+                to_offset_datetime(date).map(LocalDateTime::Single)
+            }
+            [FoundDateTimeKind::Normal(first), FoundDateTimeKind::Normal(second), ..] => {
+                to_offset_datetime(first)
+                    .zip(to_offset_datetime(second))
+                    .map(|(first, second)| LocalDateTime::Ambiguous(first, second))
+            }
+            [FoundDateTimeKind::Skipped {
+                before_transition,
+                after_transition,
+            }, ..] => {
+                // Interpret the wall-clock time with the offset before the
+                // transition, which shifts it forward by the length of the gap.
+                let before = to_utc_offset(before_transition.local_time_type());
+                let after = to_utc_offset(after_transition.local_time_type());
+
+                local
+                    .assume_offset(before)
+                    .checked_to_offset(after)
+                    .map(LocalDateTime::Skipped)
+            }
+            _ => None,
+        };
+
+        // Dates that are out of the range of the time zone data or close to
+        // the limits of the time crate keep the offset of the nearest
+        // transition.
+        resolved.unwrap_or_else(|| {
+            LocalDateTime::Single(local.assume_offset(self.offset_at(local.assume_utc())))
+        })
+    }
+}
+
+impl fmt::Debug for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TimeZone").field(&self.name).finish()
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl PartialEq for TimeZone {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && (Arc::ptr_eq(&self.inner, &other.inner) || self.inner == other.inner)
+    }
+}
+
+impl Eq for TimeZone {}
+
+// Converts a local time type of the tz crate into an UtcOffset.
+fn to_utc_offset(local_time_type: &LocalTimeType) -> UtcOffset {
+    UtcOffset::from_whole_seconds(local_time_type.ut_offset()).unwrap_or(UtcOffset::UTC)
+}
+
+// Converts a date of the tz crate into an OffsetDateTime, or returns None if
+// the wall-clock time is out of the range of the time crate.
+fn to_offset_datetime(date: &DateTime) -> Option<OffsetDateTime> {
+    let month = Month::try_from(date.month()).ok()?;
+    let day = Date::from_calendar_date(date.year(), month, date.month_day()).ok()?;
+    let time = Time::from_hms_nano(
+        date.hour(),
+        date.minute(),
+        date.second(),
+        date.nanoseconds(),
+    )
+    .ok()?;
+
+    Some(PrimitiveDateTime::new(day, time).assume_offset(to_utc_offset(date.local_time_type())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{datetime, offset};
+
+    #[test]
+    fn test_named() {
+        assert!(TimeZone::named("Europe/Berlin").is_ok());
+        assert_eq!(
+            TimeZone::named("Europe/Atlantis").unwrap_err(),
+            Error::UnknownTimeZone("Europe/Atlantis".to_string())
+        );
+        assert_eq!(
+            TimeZone::named("../../etc/passwd").unwrap_err(),
+            Error::UnknownTimeZone("../../etc/passwd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let zone = TimeZone::named("Europe/Berlin").unwrap();
+
+        assert_eq!(
+            zone.resolve(datetime!(2024-07-01 06:00:00)),
+            LocalDateTime::Single(datetime!(2024-07-01 06:00:00 +2))
+        );
+        assert_eq!(
+            zone.resolve(datetime!(2024-03-31 02:30:00)),
+            LocalDateTime::Skipped(datetime!(2024-03-31 03:30:00 +2))
+        );
+        assert_eq!(
+            zone.resolve(datetime!(2024-10-27 02:30:00)),
+            LocalDateTime::Ambiguous(
+                datetime!(2024-10-27 02:30:00 +2),
+                datetime!(2024-10-27 02:30:00 +1)
+            )
+        );
+        assert_eq!(
+            zone.offset_at(datetime!(2024-01-01 00:00:00 UTC)),
+            offset!(+1)
+        );
+    }
+
+    // A zone that moves from +01:00 to +05:00 at the start of 2000 and has no
+    // rule for the time after that transition.
+    fn test_zone() -> TimeZone {
+        let inner = tz::TimeZone::new(
+            vec![tz::timezone::Transition::new(946684800, 1)],
+            vec![
+                LocalTimeType::with_ut_offset(3600).unwrap(),
+                LocalTimeType::with_ut_offset(18000).unwrap(),
+            ],
+            vec![],
+            None,
+        )
+        .unwrap();
+
+        TimeZone {
+            name: "Test/Zone".to_string(),
+            inner: Arc::new(inner),
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        let berlin = TimeZone::named("Europe/Berlin").unwrap();
+
+        assert_eq!(berlin, berlin.clone());
+        assert_eq!(berlin, TimeZone::named("Europe/Berlin").unwrap());
+        assert_ne!(berlin, TimeZone::utc());
+        assert_ne!(
+            berlin,
+            TimeZone {
+                name: "Europe/Berlin".to_string(),
+                inner: Arc::new(tz::TimeZone::utc()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_max_transition() {
+        assert_eq!(TimeZone::utc().max_transition(), Duration::ZERO);
+        assert_eq!(test_zone().max_transition(), Duration::hours(4));
+        assert!(TimeZone::named("Europe/Berlin").unwrap().max_transition() >= Duration::hours(2));
+    }
+
+    #[test]
+    fn test_resolve_edges() {
+        // The instants are out of the range of the time crate in UTC.
+        let zone = TimeZone::named("America/New_York").unwrap();
+        assert_eq!(
+            zone.resolve(datetime!(9999-12-31 23:00:00)),
+            LocalDateTime::Single(datetime!(9999-12-31 23:00:00 -5))
+        );
+
+        let zone = TimeZone::named("Asia/Tokyo").unwrap();
+        let offset = zone.offset_at(datetime!(-9999-01-01 00:00:00 UTC));
+        assert_eq!(
+            zone.resolve(datetime!(-9999-01-01 01:00:00)),
+            LocalDateTime::Single(datetime!(-9999-01-01 01:00:00).assume_offset(offset))
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let zone = test_zone();
+
+        assert_eq!(
+            zone.offset_at(datetime!(1999-12-31 00:00:00 UTC)),
+            offset!(+1)
+        );
+        assert_eq!(
+            zone.offset_at(datetime!(2100-01-01 00:00:00 UTC)),
+            offset!(+5)
+        );
+        assert_eq!(
+            zone.resolve(datetime!(2100-01-01 06:00:00)),
+            LocalDateTime::Single(datetime!(2100-01-01 06:00:00 +5))
+        );
+        assert_eq!(
+            zone.resolve(datetime!(1999-12-31 06:00:00)),
+            LocalDateTime::Single(datetime!(1999-12-31 06:00:00 +1))
+        );
+    }
+}
@@ -1,9 +1,25 @@
-use time::{Time, Weekday};
+use crate::calendar::RollPolicy;
+#[cfg(feature = "tz")]
+use crate::timezone::TimeZone;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum TimeSpec {
+    EveryFullHour,
+    List(Vec<Time>),
+    Interval {
+        step: Duration,
+        start: Time,
+        end: Time,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum WeekVariant {
     Even,
     Odd,
+    Cycle { interval: u16, anchor: Date },
+    Numbers(Vec<u8>),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord)]
@@ -12,12 +28,97 @@ pub(crate) enum WeekdayModifier {
     Second,
     Third,
     Fourth,
+    Fifth,
+    Last,
+    SecondToLast,
+    ThirdToLast,
+    FourthToLast,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum MonthDay {
+    Day(u8),
+    Last,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct MonthDays {
+    pub days: Vec<MonthDay>,
+    pub clamp: bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum BusinessDays {
+    Every,
+    First,
     Last,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) struct ParsedSchedule {
-    pub times: Vec<Time>,
+    pub times: TimeSpec,
     pub days: Option<Vec<(Weekday, Option<WeekdayModifier>)>>,
+    pub monthdays: Option<MonthDays>,
+    pub business_days: Option<BusinessDays>,
+    pub months: Option<Vec<Month>>,
     pub weeks: Option<WeekVariant>,
+    pub roll: Option<RollPolicy>,
+    pub exceptions: Option<Vec<Exception>>,
+    pub zone: Option<Zone>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum Exception {
+    Date(Date),
+    DateRange(Date, Date),
+    Day(Month, u8),
+    DayRange((Month, u8), (Month, u8)),
+    Weekday(Weekday),
+    Holidays(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum Zone {
+    Offset(UtcOffset),
+    #[cfg(feature = "tz")]
+    TimeZone(TimeZone),
+}
+
+impl Zone {
+    // Returns the offset from UTC that is in effect at some instant.
+    #[cfg_attr(not(feature = "tz"), allow(unused_variables))]
+    pub fn offset_at(&self, instant: OffsetDateTime) -> UtcOffset {
+        match self {
+            Zone::Offset(offset) => *offset,
+            #[cfg(feature = "tz")]
+            Zone::TimeZone(zone) => zone.offset_at(instant),
+        }
+    }
+
+    // Returns the maximum amount of time by which the offset of the zone
+    // changes at once.
+    pub fn max_transition(&self) -> Duration {
+        match self {
+            Zone::Offset(_) => Duration::ZERO,
+            #[cfg(feature = "tz")]
+            Zone::TimeZone(zone) => zone.max_transition(),
+        }
+    }
+
+    // Maps a wall-clock time to the instant(s) it refers to in this zone.
+    pub fn resolve(&self, local: PrimitiveDateTime) -> LocalDateTime {
+        match self {
+            Zone::Offset(offset) => LocalDateTime::Single(local.assume_offset(*offset)),
+            #[cfg(feature = "tz")]
+            Zone::TimeZone(zone) => zone.resolve(local),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "tz"), allow(dead_code))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum LocalDateTime {
+    Single(OffsetDateTime),
+    Ambiguous(OffsetDateTime, OffsetDateTime),
+    Skipped(OffsetDateTime),
 }
@@ -15,9 +15,6 @@ fn test_unexpected_end_of_input() {
     let result = Schedule::from_str("at ").unwrap_err();
     assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
 
-    let result = Schedule::from_str("at 08").unwrap_err();
-    assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
-
     let result = Schedule::from_str("at 8:").unwrap_err();
     assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
 
@@ -145,3 +142,196 @@ fn test_schedule_13() {
     let result = Schedule::from_str(expr);
     assert!(result.is_ok(), "{:?}", result);
 }
+
+#[test]
+fn test_schedule_14() {
+    let expr = "at every full hour on Mondays in odd weeks";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_15() {
+    let expr = "every 2 hours between 8 AM and 6 PM on Saturdays";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_invalid_interval() {
+    let result = Schedule::from_str("every 0 minutes").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("every 15 minutes between 5 PM and 9 AM").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+}
+
+#[test]
+fn test_schedule_16() {
+    let expr = "at 18:30 on Fridays";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_17() {
+    let expr = "at 7:30, 12 PM and 23:45 (Mondays and Thursdays)";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_ambiguous_time() {
+    let result = Schedule::from_str("at 12").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 08").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 12 on Mondays").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 24:00").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::TimeParse(_)));
+}
+
+#[test]
+fn test_schedule_18() {
+    let expr = "at 9 AM on the 1st and 15th of the month";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_19() {
+    let expr = "at 6 PM on the 30th of the month or the last day in odd weeks";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_invalid_monthday() {
+    let result = Schedule::from_str("at 6 AM on the 32nd of the month").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 6 AM on the 2th of the month").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 6 AM on the 15th").unwrap_err();
+    assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
+}
+
+#[test]
+fn test_schedule_20() {
+    let expr = "at 6 AM on the first Monday in January, April, July and October";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_21() {
+    let expr = "at 6 AM on Saturdays from March to October in even weeks";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_22() {
+    let expr = "at 9 AM on weekends";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_23() {
+    let expr = "at 6 PM (Fri-Mon) in odd weeks";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_24() {
+    let expr = "at 6 AM in odd weeks";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_25() {
+    let expr = "at 6 AM on Mondays every 3rd week starting 2024-01-01";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_invalid_week_cycle() {
+    let result = Schedule::from_str("at 6 AM every 3rd week starting 2024-13-01").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::TimeParse(_)));
+
+    let result = Schedule::from_str("at 6 AM every 3rd week starting 2024").unwrap_err();
+    assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
+}
+
+#[test]
+fn test_schedule_26() {
+    let expr = "at 10 AM on Thursdays in weeks 10 to 20";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_invalid_week_number() {
+    let result = Schedule::from_str("at 10 AM in week 54").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+}
+
+#[test]
+fn test_schedule_27() {
+    let expr = "at 6 PM on the 5th Friday and the second-to-last Thursday";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_28() {
+    let expr = "at 8 AM on weekdays except on public holidays and December 24 to January 1";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_invalid_exception() {
+    let result = Schedule::from_str("at 8 AM except on February 30").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+
+    let result = Schedule::from_str("at 8 AM except on public").unwrap_err();
+    assert_eq!(result, cron_lingo::error::Error::UnexpectedEndOfInput);
+
+    let result = Schedule::from_str("at 8 AM except on Sundays in odd weeks").unwrap_err();
+    assert!(matches!(result, cron_lingo::error::Error::Syntax(_)));
+}
+
+#[test]
+fn test_schedule_29() {
+    let expr = "at 6 PM on the last business day of the month except on December 31";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_30() {
+    let expr = "at 9 AM on the 1st of the month or the following business day";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_schedule_31() {
+    let expr = "at 9 AM on Mondays UTC";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+
+    let expr = "at 9 AM on the 1st of the month +02:00";
+    let result = Schedule::from_str(expr);
+    assert!(result.is_ok(), "{:?}", result);
+}